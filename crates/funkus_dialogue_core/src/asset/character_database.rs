@@ -0,0 +1,291 @@
+//! # CharacterDatabase Definition
+//!
+//! This module defines the asset type that describes the characters appearing in dialogues.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::error::UnknownSpeakersError;
+use crate::graph::{DialogueElement, DialogueGraph};
+
+/// Presentation data for a single character.
+///
+/// A `CharacterDefinition` holds everything the presentation layer needs to know
+/// about a speaker, so that dialogue nodes only have to reference the speaker ID.
+///
+/// # Fields
+///
+/// * `display_name` - Name shown in the dialogue box
+/// * `name_key` - Optional localization key for the name
+/// * `name_color` - Optional hex color for the name (e.g. `"#FFD966"`)
+/// * `default_portrait` - Portrait used when a node doesn't specify one
+/// * `voice_blip` - Optional path to the sound played while the character talks
+/// * `text_speed` - Optional text reveal speed in characters per second
+#[derive(Debug, Clone, Default, Reflect, Serialize, Deserialize)]
+#[serde(crate = "serde")]
+pub struct CharacterDefinition {
+    /// Name shown in the dialogue box
+    pub display_name: String,
+    /// Optional localization key for the name
+    #[serde(default)]
+    pub name_key: Option<String>,
+    /// Optional hex color for the name
+    #[serde(default)]
+    pub name_color: Option<String>,
+    /// Portrait used when a node doesn't specify one
+    #[serde(default)]
+    pub default_portrait: Option<String>,
+    /// Optional path to the sound played while the character talks
+    #[serde(default)]
+    pub voice_blip: Option<String>,
+    /// Optional text reveal speed in characters per second
+    #[serde(default)]
+    pub text_speed: Option<f32>,
+}
+
+impl CharacterDefinition {
+    /// Creates a new character definition with the given display name.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use funkus_dialogue_core::CharacterDefinition;
+    ///
+    /// let guide = CharacterDefinition::new("Guide");
+    /// assert_eq!(guide.display_name, "Guide");
+    /// ```
+    pub fn new(display_name: impl Into<String>) -> Self {
+        Self {
+            display_name: display_name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Parses `name_color` into a Bevy color.
+    ///
+    /// # Returns
+    ///
+    /// The parsed color, or None if no color is set or the hex string is invalid
+    pub fn color(&self) -> Option<Color> {
+        self.name_color
+            .as_deref()
+            .and_then(|hex| Srgba::hex(hex).ok())
+            .map(Color::from)
+    }
+}
+
+/// Asset containing all characters that can speak in dialogues.
+///
+/// Characters are keyed by speaker ID. The `speaker` field of a
+/// [`DialogueNode`](crate::DialogueNode) holds one of these IDs, so renaming a
+/// character only requires editing the database.
+///
+/// Speakers that are not present in the database are still displayed using
+/// the raw speaker string, which keeps free-form speakers working.
+///
+/// # Example JSON Format
+///
+/// ```json
+/// {
+///   "characters": {
+///     "guide": {
+///       "display_name": "Guide",
+///       "name_key": "character.guide.name",
+///       "name_color": "#FFD966",
+///       "default_portrait": "portraits/guide.png",
+///       "voice_blip": "sounds/blip_guide.ogg",
+///       "text_speed": 40.0
+///     }
+///   }
+/// }
+/// ```
+#[derive(Asset, Debug, Clone, Default, Reflect, Serialize, Deserialize)]
+#[serde(crate = "serde")]
+pub struct CharacterDatabase {
+    /// Character definitions keyed by speaker ID
+    pub characters: HashMap<String, CharacterDefinition>,
+}
+
+impl CharacterDatabase {
+    /// Builder method to add a character to the database.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use funkus_dialogue_core::{CharacterDatabase, CharacterDefinition};
+    ///
+    /// let database = CharacterDatabase::default()
+    ///     .with_character("guide", CharacterDefinition::new("Guide"));
+    /// assert!(database.contains("guide"));
+    /// ```
+    pub fn with_character(
+        mut self,
+        speaker_id: impl Into<String>,
+        character: CharacterDefinition,
+    ) -> Self {
+        self.characters.insert(speaker_id.into(), character);
+        self
+    }
+
+    /// Gets a character by speaker ID.
+    pub fn get(&self, speaker_id: &str) -> Option<&CharacterDefinition> {
+        self.characters.get(speaker_id)
+    }
+
+    /// Checks if a character with the given speaker ID exists.
+    pub fn contains(&self, speaker_id: &str) -> bool {
+        self.characters.contains_key(speaker_id)
+    }
+
+    /// Resolves the name to display for a speaker.
+    ///
+    /// # Returns
+    ///
+    /// The character's display name, or the speaker ID itself if the speaker
+    /// is not in the database
+    pub fn display_name<'a>(&'a self, speaker_id: &'a str) -> &'a str {
+        self.get(speaker_id)
+            .map(|character| character.display_name.as_str())
+            .unwrap_or(speaker_id)
+    }
+
+    /// Resolves the default portrait of a speaker.
+    ///
+    /// # Returns
+    ///
+    /// The character's default portrait, or None if the speaker is not in the
+    /// database or has no default portrait
+    pub fn default_portrait(&self, speaker_id: &str) -> Option<&str> {
+        self.get(speaker_id)?.default_portrait.as_deref()
+    }
+
+    /// Checks that every speaker referenced by the graph exists in the database.
+    ///
+    /// # Parameters
+    ///
+    /// * `graph` - The dialogue graph to check
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if all speakers are known, or an error listing the nodes with an
+    /// unknown speaker
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use funkus_dialogue_core::{CharacterDatabase, CharacterDefinition};
+    /// use funkus_dialogue_core::graph::{DialogueGraph, DialogueNode, NodeId};
+    ///
+    /// let database = CharacterDatabase::default()
+    ///     .with_character("guide", CharacterDefinition::new("Guide"));
+    ///
+    /// let graph = DialogueGraph::new(NodeId(1))
+    ///     .with_node(DialogueNode::text(NodeId(1), "Hello").with_speaker("guide"))
+    ///     .with_node(DialogueNode::text(NodeId(2), "Hi").with_speaker("stranger"));
+    ///
+    /// let err = database.validate_speakers(&graph).unwrap_err();
+    /// assert_eq!(err.speakers, vec![(NodeId(2), "stranger".to_string())]);
+    /// ```
+    pub fn validate_speakers(&self, graph: &DialogueGraph) -> Result<(), UnknownSpeakersError> {
+        let mut speakers: Vec<_> = graph
+            .nodes_iter()
+            .filter_map(|node| {
+                let speaker = node.speaker()?;
+                (!self.contains(speaker)).then(|| (node.id(), speaker.to_string()))
+            })
+            .collect();
+
+        if speakers.is_empty() {
+            return Ok(());
+        }
+
+        speakers.sort_by_key(|(id, _)| id.0);
+        Err(UnknownSpeakersError { speakers })
+    }
+}
+
+/// Resource pointing at the character database used to resolve speakers.
+///
+/// Insert this resource to let the dialogue UI and validation resolve speaker IDs.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use funkus_dialogue_core::DialogueCharacters;
+///
+/// fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+///     commands.insert_resource(DialogueCharacters(
+///         asset_server.load("dialogues/example.characters.json"),
+///     ));
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, Default)]
+pub struct DialogueCharacters(pub Handle<CharacterDatabase>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DialogueNode, NodeId};
+
+    #[test]
+    fn test_validate_speakers() {
+        let database =
+            CharacterDatabase::default().with_character("guide", CharacterDefinition::new("Guide"));
+
+        let mut graph = DialogueGraph::new(NodeId(1))
+            .with_node(DialogueNode::text(NodeId(1), "Hello").with_speaker("guide"))
+            .with_node(DialogueNode::text(NodeId(2), "No speaker"));
+        assert!(database.validate_speakers(&graph).is_ok());
+
+        graph.add_node(DialogueNode::choice(NodeId(3)).with_speaker("stranger"));
+        graph.add_node(DialogueNode::text(NodeId(4), "Who?").with_speaker("ghost"));
+        let err = database.validate_speakers(&graph).unwrap_err();
+        assert_eq!(
+            err.speakers,
+            vec![
+                (NodeId(3), "stranger".to_string()),
+                (NodeId(4), "ghost".to_string()),
+            ]
+        );
+        assert!(err.to_string().contains("NodeId(3) ('stranger')"));
+    }
+
+    #[test]
+    fn test_display_name_fallback() {
+        let database = CharacterDatabase::default().with_character(
+            "guide",
+            CharacterDefinition {
+                display_name: "The Guide".to_string(),
+                name_color: Some("#FFD966".to_string()),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(database.display_name("guide"), "The Guide");
+        assert_eq!(database.display_name("stranger"), "stranger");
+        assert!(database.get("guide").unwrap().color().is_some());
+    }
+
+    #[test]
+    fn test_default_portrait() {
+        let database = CharacterDatabase::default()
+            .with_character(
+                "guide",
+                CharacterDefinition {
+                    display_name: "Guide".to_string(),
+                    default_portrait: Some("portraits/guide.png".to_string()),
+                    ..Default::default()
+                },
+            )
+            .with_character("narrator", CharacterDefinition::new("Narrator"));
+
+        assert_eq!(
+            database.default_portrait("guide"),
+            Some("portraits/guide.png")
+        );
+        assert_eq!(database.default_portrait("narrator"), None);
+        assert_eq!(database.default_portrait("stranger"), None);
+    }
+}
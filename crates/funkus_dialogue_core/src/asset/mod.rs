@@ -7,12 +7,14 @@
 //! The asset system is responsible for:
 //!
 //! - Defining the `DialogueAsset` type that represents dialogue data
+//! - Defining the `CharacterDatabase` type that describes speakers
 //! - Loading dialogue data from JSON files
 //! - Providing access to dialogue data for the runtime system
 //!
 //! ## Key Components
 //!
 //! - [`DialogueAsset`]: The main asset type that contains a dialogue graph and metadata
+//! - [`CharacterDatabase`]: Asset mapping speaker IDs to names, colors, portraits and voices
 //!
 //! ## Usage
 //!
@@ -24,6 +26,8 @@
 //! }
 //! ```
 
+mod character_database;
 mod dialogue_asset;
 
+pub use character_database::*;
 pub use dialogue_asset::*;
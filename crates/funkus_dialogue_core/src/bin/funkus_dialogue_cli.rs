@@ -0,0 +1,149 @@
+//! Command line tools for dialogue assets.
+//!
+//! ```text
+//! funkus_dialogue_cli export <dot|mermaid> <directory> [output directory]
+//! ```
+//!
+//! `export` renders every `*.dialogue.json` file found in the directory tree as a
+//! Graphviz DOT (`.dot`) or Mermaid (`.mmd`) diagram. Diagrams are written next to
+//! their dialogue, or mirrored into the output directory when one is given.
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use funkus_dialogue_core::DialogueAsset;
+
+const USAGE: &str =
+    "Usage: funkus_dialogue_cli export <dot|mermaid> <directory> [output directory]";
+
+/// Supported diagram formats
+#[derive(Clone, Copy)]
+enum ExportFormat {
+    Dot,
+    Mermaid,
+}
+
+impl ExportFormat {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "dot" => Some(Self::Dot),
+            "mermaid" => Some(Self::Mermaid),
+            _ => None,
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Dot => "dot",
+            Self::Mermaid => "mmd",
+        }
+    }
+
+    fn render(self, dialogue: &DialogueAsset) -> String {
+        match self {
+            Self::Dot => dialogue.graph.to_dot(),
+            Self::Mermaid => dialogue.graph.to_mermaid(),
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match args.as_slice() {
+        ["export", format, input] | ["export", format, input, _] => {
+            let Some(format) = ExportFormat::parse(format) else {
+                eprintln!("Unknown format '{}', expected 'dot' or 'mermaid'", format);
+                return ExitCode::FAILURE;
+            };
+            let input = Path::new(input);
+            let output = args.get(3).map(Path::new).unwrap_or(input);
+
+            export_directory(format, input, output)
+        }
+        _ => {
+            eprintln!("{}", USAGE);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Exports every dialogue found under `input` into `output`.
+fn export_directory(format: ExportFormat, input: &Path, output: &Path) -> ExitCode {
+    let mut files = Vec::new();
+    if let Err(err) = find_dialogues(input, &mut files) {
+        eprintln!("Failed to read {}: {}", input.display(), err);
+        return ExitCode::FAILURE;
+    }
+    files.sort();
+
+    let mut failures = 0;
+    for file in &files {
+        match export_file(format, input, output, file) {
+            Ok(target) => println!("{} -> {}", file.display(), target.display()),
+            Err(err) => {
+                eprintln!("{}: {}", file.display(), err);
+                failures += 1;
+            }
+        }
+    }
+
+    println!(
+        "Exported {} of {} dialogues",
+        files.len() - failures,
+        files.len()
+    );
+    if failures == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Recursively collects the `*.dialogue.json` files of a directory.
+fn find_dialogues(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            find_dialogues(&path, files)?;
+        } else if path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.ends_with(".dialogue.json"))
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Exports a single dialogue file and returns the path of the diagram.
+fn export_file(
+    format: ExportFormat,
+    input: &Path,
+    output: &Path,
+    file: &Path,
+) -> Result<PathBuf, String> {
+    let json = std::fs::read_to_string(file).map_err(|err| err.to_string())?;
+    let dialogue: DialogueAsset = serde_json::from_str(&json).map_err(|err| err.to_string())?;
+
+    // Mirror the location of the dialogue inside the output directory
+    let relative = file.strip_prefix(input).unwrap_or(file);
+    let file_name = relative
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.strip_suffix(".dialogue.json"))
+        .ok_or_else(|| "Invalid dialogue file name".to_string())?;
+    let target =
+        output
+            .join(relative)
+            .with_file_name(format!("{}.{}", file_name, format.extension()));
+
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    std::fs::write(&target, format.render(&dialogue)).map_err(|err| err.to_string())?;
+
+    Ok(target)
+}
@@ -1,10 +1,15 @@
 //! Debug utilities for the dialogue system.
 
 use bevy::prelude::*;
+use bevy::utils::{HashMap, HashSet};
 use bevy_egui::{egui, EguiContexts, EguiPlugin};
+use std::collections::VecDeque;
 
 use crate::{
-    graph::NodeId,
+    asset::DialogueAsset,
+    events::{AdvanceDialogue, JumpDialogue, SelectDialogueChoice},
+    graph::DialogueGraph,
+    graph::{DialogueElement, DialogueNode, NodeId},
     runtime::{DialogueRunner, DialogueState},
 };
 
@@ -14,8 +19,11 @@ pub struct DialogueDebugPlugin;
 impl Plugin for DialogueDebugPlugin {
     fn build(&self, app: &mut App) {
         // Make sure EguiPlugin is added
-        app.add_plugins(EguiPlugin)
-            .register_type::<DialogueState>()
+        if !app.is_plugin_added::<EguiPlugin>() {
+            app.add_plugins(EguiPlugin);
+        }
+
+        app.register_type::<DialogueState>()
             .register_type::<NodeId>()
             .register_type::<Option<NodeId>>()
             .init_resource::<DialogueDebugState>()
@@ -32,13 +40,39 @@ pub struct DialogueDebugState {
     pub visible: bool,
     /// ID of the currently selected entity
     pub selected_entity: Option<Entity>,
+    /// Node ID entered in the jump field
+    pub jump_target: u32,
+    /// Name of the variable being added
+    pub new_variable_name: String,
+    /// Value of the variable being added
+    pub new_variable_value: String,
+    /// Whether the graph view of the selected runner is shown
+    pub show_graph: bool,
+}
+
+/// Action requested from the inspector, applied once the UI has been drawn.
+enum InspectorAction {
+    /// Make the given node the current node
+    Jump(NodeId),
+    /// Select the given choice and advance past it
+    ForceChoice(usize),
 }
 
 /// System that displays debug information about dialogue.
+///
+/// The window lists every dialogue runner. Selecting one shows its current node,
+/// state and outgoing connections, and an editable table of its variables.
+/// Connections and the jump field move the runner to any node, and choices can be
+/// forced to reach deep branches without replaying the conversation.
+#[allow(clippy::too_many_arguments)]
 fn debug_ui_system(
     mut state: ResMut<DialogueDebugState>,
     keyboard_input: Res<ButtonInput<KeyCode>>,
-    dialogue_runners: Query<(Entity, &DialogueRunner, &Name)>,
+    dialogue_assets: Res<Assets<DialogueAsset>>,
+    mut dialogue_runners: Query<(Entity, &mut DialogueRunner, Option<&Name>)>,
+    mut jump_events: EventWriter<JumpDialogue>,
+    mut select_events: EventWriter<SelectDialogueChoice>,
+    mut advance_events: EventWriter<AdvanceDialogue>,
     mut contexts: EguiContexts,
 ) {
     // Toggle debug UI with F1
@@ -55,14 +89,25 @@ fn debug_ui_system(
         return;
     }
 
-    // Create a very simple debug window
-    egui::Window::new("Dialogue Debug").show(contexts.ctx_mut(), |ui| {
+    // Forget runners that have been despawned
+    if let Some(entity) = state.selected_entity {
+        if !dialogue_runners.contains(entity) {
+            state.selected_entity = None;
+        }
+    }
+
+    let mut action = None;
+    let ctx = contexts.ctx_mut().clone();
+
+    egui::Window::new("Dialogue Debug").show(&ctx, |ui| {
         ui.heading("Dialogue Entities");
 
         // List all dialogue entities
         for (entity, runner, name) in dialogue_runners.iter() {
+            let name = name.map_or("Dialogue Runner", |name| name.as_str());
             let text = format!("{} ({:?}) - State: {:?}", name, entity, runner.state);
-            if ui.button(text).clicked() {
+            let selected = state.selected_entity == Some(entity);
+            if ui.selectable_label(selected, text).clicked() {
                 state.selected_entity = Some(entity);
             }
         }
@@ -70,5 +115,384 @@ fn debug_ui_system(
         if dialogue_runners.is_empty() {
             ui.label("No dialogue entities found");
         }
+
+        let Some(entity) = state.selected_entity else {
+            return;
+        };
+        let Ok((_, mut runner, _)) = dialogue_runners.get_mut(entity) else {
+            return;
+        };
+
+        ui.separator();
+        ui.heading("Current Node");
+        ui.label(format!("State: {:?}", runner.state));
+        ui.checkbox(&mut state.show_graph, "Show graph");
+
+        let Some(dialogue) = dialogue_assets.get(&runner.dialogue_handle) else {
+            ui.label("Dialogue asset not loaded");
+            return;
+        };
+
+        match runner.current_node(dialogue) {
+            Some(node) => {
+                ui.label(format!("Node: {:?}", node.id()));
+                match node {
+                    DialogueNode::Text {
+                        variants,
+                        variant_mode,
+                        speaker,
+                        ..
+                    } => {
+                        ui.label(format!("Speaker: {}", speaker.as_deref().unwrap_or("-")));
+                        ui.label(format!(
+                            "Text: {}",
+                            runner.current_text(dialogue).unwrap_or_default()
+                        ));
+                        if !variants.is_empty() {
+                            ui.label(format!(
+                                "Line {} of {} ({:?})",
+                                runner.current_line + 1,
+                                variants.len() + 1,
+                                variant_mode
+                            ));
+                        }
+                    }
+                    DialogueNode::Choice {
+                        prompt,
+                        hub,
+                        speaker,
+                        ..
+                    } => {
+                        ui.label(format!("Speaker: {}", speaker.as_deref().unwrap_or("-")));
+                        ui.label(format!("Prompt: {}", prompt.as_deref().unwrap_or("-")));
+                        if *hub {
+                            ui.label("Hub");
+                        }
+                        if let Some(time_left) = runner.choice_time_left(dialogue) {
+                            ui.label(format!("Time left: {:.1}s", time_left));
+                        }
+                    }
+                    DialogueNode::Random { mode, .. } => {
+                        ui.label(format!("Random node: {:?}", mode));
+                    }
+                    DialogueNode::Wait { duration, .. } => {
+                        ui.label(format!(
+                            "Waiting: {:.1}s / {:.1}s",
+                            runner.auto_advance_timer.elapsed_secs(),
+                            duration
+                        ));
+                    }
+                    DialogueNode::Input {
+                        prompt,
+                        variable,
+                        rules,
+                        ..
+                    } => {
+                        ui.label(format!("Prompt: {}", prompt.as_deref().unwrap_or("-")));
+                        ui.label(format!(
+                            "Variable: {} = {}",
+                            variable,
+                            runner.variables.get(variable).map_or("-", String::as_str)
+                        ));
+                        for rule in rules {
+                            ui.label(format!("  Input {}", rule));
+                        }
+                    }
+                    DialogueNode::Return { .. } => {
+                        ui.label(format!("Return to hub: {:?}", runner.hubs.last()));
+                    }
+                    DialogueNode::End { outcome, .. } => {
+                        ui.label(format!("Outcome: {}", outcome.as_deref().unwrap_or("-")));
+                    }
+                    DialogueNode::Custom {
+                        node_type, data, ..
+                    } => {
+                        ui.label(format!("Custom node: {}", node_type));
+                        ui.label(format!("Data: {}", data));
+                    }
+                }
+
+                ui.label("Connections:");
+                let is_choice = matches!(node, DialogueNode::Choice { .. });
+                let connections = dialogue.graph.get_connections(node.id());
+                if connections.is_empty() {
+                    ui.label("  (end of dialogue)");
+                }
+                // Taken once-only choices don't count in choice indices
+                let mut choice_index = 0;
+                for (index, (target, data)) in connections.into_iter().enumerate() {
+                    let taken = data.once && runner.taken_choices.contains(&(node.id(), target));
+                    ui.horizontal(|ui| {
+                        ui.label(format!(
+                            "{}. {:?} {}{}",
+                            index + 1,
+                            target,
+                            data.label.as_deref().unwrap_or_default(),
+                            if taken { " (taken)" } else { "" }
+                        ));
+                        if ui.small_button("Jump").clicked() {
+                            action = Some(InspectorAction::Jump(target));
+                        }
+                        if is_choice && !taken && ui.small_button("Choose").clicked() {
+                            action = Some(InspectorAction::ForceChoice(choice_index));
+                        }
+                    });
+                    if !taken {
+                        choice_index += 1;
+                    }
+                }
+            }
+            None => {
+                ui.label("No current node");
+            }
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("Jump to node:");
+            ui.add(egui::DragValue::new(&mut state.jump_target));
+            if ui.button("Jump").clicked() {
+                action = Some(InspectorAction::Jump(NodeId(state.jump_target)));
+            }
+        });
+
+        ui.separator();
+        ui.heading("Variables");
+
+        let mut keys: Vec<_> = runner.variables.keys().cloned().collect();
+        keys.sort();
+        let mut removed = None;
+        egui::Grid::new("dialogue_debug_variables")
+            .num_columns(3)
+            .show(ui, |ui| {
+                for key in keys {
+                    ui.label(key.as_str());
+                    if let Some(value) = runner.variables.get_mut(&key) {
+                        ui.text_edit_singleline(value);
+                    }
+                    if ui.small_button("x").clicked() {
+                        removed = Some(key);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(key) = removed {
+            runner.variables.remove(&key);
+        }
+
+        ui.horizontal(|ui| {
+            ui.text_edit_singleline(&mut state.new_variable_name);
+            ui.text_edit_singleline(&mut state.new_variable_value);
+            if ui.button("Add").clicked() && !state.new_variable_name.is_empty() {
+                let name = std::mem::take(&mut state.new_variable_name);
+                let value = std::mem::take(&mut state.new_variable_value);
+                runner.variables.insert(name, value);
+            }
+        });
     });
+
+    // Show the graph of the selected runner
+    if state.show_graph {
+        let selected = state
+            .selected_entity
+            .and_then(|entity| dialogue_runners.get(entity).ok());
+        if let Some((_, runner, _)) = selected {
+            if let Some(dialogue) = dialogue_assets.get(&runner.dialogue_handle) {
+                let mut open = true;
+                egui::Window::new("Dialogue Graph")
+                    .open(&mut open)
+                    .default_size([600.0, 400.0])
+                    .show(&ctx, |ui| {
+                        if let Some(node_id) = graph_view(ui, &dialogue.graph, runner) {
+                            action = Some(InspectorAction::Jump(node_id));
+                        }
+                    });
+                state.show_graph = open;
+            }
+        }
+    }
+
+    // Apply the requested action to the selected runner
+    let (Some(action), Some(entity)) = (action, state.selected_entity) else {
+        return;
+    };
+
+    match action {
+        InspectorAction::Jump(node_id) => {
+            jump_events.send(JumpDialogue { entity, node_id });
+        }
+        InspectorAction::ForceChoice(choice_index) => {
+            select_events.send(SelectDialogueChoice {
+                entity,
+                choice_index,
+            });
+            advance_events.send(AdvanceDialogue { entity });
+        }
+    }
+}
+
+/// Size of a node box in the graph view
+const GRAPH_NODE_SIZE: egui::Vec2 = egui::vec2(140.0, 36.0);
+/// Space between node boxes in the graph view
+const GRAPH_NODE_SPACING: egui::Vec2 = egui::vec2(40.0, 50.0);
+
+/// Computes a layered layout for a dialogue graph.
+///
+/// Nodes are placed in rows by their breadth-first distance from the start node
+/// or the nearest entry point. Nodes that can't be reached are placed in a final row.
+///
+/// # Returns
+///
+/// The row and column of every node
+fn layout_graph(graph: &DialogueGraph) -> HashMap<NodeId, (usize, usize)> {
+    let mut depths = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    let entry_nodes = graph
+        .entry_points
+        .iter()
+        .map(|entry_point| entry_point.node_id);
+    for id in std::iter::once(graph.start_node).chain(entry_nodes) {
+        if graph.contains_node(id) && !depths.contains_key(&id) {
+            depths.insert(id, 0);
+            queue.push_back(id);
+        }
+    }
+
+    while let Some(id) = queue.pop_front() {
+        let depth = depths[&id];
+        for (target, _) in graph.get_connected_nodes(id) {
+            if !depths.contains_key(&target) {
+                depths.insert(target, depth + 1);
+                queue.push_back(target);
+            }
+        }
+    }
+
+    let unreachable_row = depths.values().max().map_or(0, |depth| depth + 1);
+    let mut ids = graph.node_ids();
+    ids.sort_by_key(|id| id.0);
+
+    let mut columns: HashMap<usize, usize> = HashMap::new();
+    ids.into_iter()
+        .map(|id| {
+            let row = depths.get(&id).copied().unwrap_or(unreachable_row);
+            let column = columns.entry(row).or_default();
+            let position = (row, *column);
+            *column += 1;
+            (id, position)
+        })
+        .collect()
+}
+
+/// Draws a dialogue graph and highlights the progress of a runner.
+///
+/// The current node, visited nodes and the path taken are highlighted.
+/// Hovering a node shows its content.
+///
+/// # Returns
+///
+/// The node that was clicked, if any
+fn graph_view(ui: &mut egui::Ui, graph: &DialogueGraph, runner: &DialogueRunner) -> Option<NodeId> {
+    let layout = layout_graph(graph);
+    let rows = layout.values().map(|(row, _)| row + 1).max().unwrap_or(0);
+    let columns = layout
+        .values()
+        .map(|(_, column)| column + 1)
+        .max()
+        .unwrap_or(0);
+    let step = GRAPH_NODE_SIZE + GRAPH_NODE_SPACING;
+
+    // Edges taken by the runner
+    let path: HashSet<(NodeId, NodeId)> = runner
+        .history
+        .windows(2)
+        .map(|pair| (pair[0], pair[1]))
+        .collect();
+
+    let mut clicked = None;
+
+    egui::ScrollArea::both().show(ui, |ui| {
+        let size = egui::vec2(columns as f32 * step.x, rows as f32 * step.y) + GRAPH_NODE_SPACING;
+        let (response, painter) = ui.allocate_painter(size, egui::Sense::hover());
+        let origin = response.rect.min + GRAPH_NODE_SPACING / 2.0;
+
+        let rect_of = |id: NodeId| {
+            layout.get(&id).map(|&(row, column)| {
+                let min = origin + egui::vec2(column as f32 * step.x, row as f32 * step.y);
+                egui::Rect::from_min_size(min, GRAPH_NODE_SIZE)
+            })
+        };
+
+        // Draw connections first so nodes are drawn on top
+        for id in graph.node_ids() {
+            let Some(from) = rect_of(id) else {
+                continue;
+            };
+            for (target, _) in graph.get_connected_nodes(id) {
+                let Some(to) = rect_of(target) else {
+                    continue;
+                };
+
+                let stroke = if path.contains(&(id, target)) {
+                    egui::Stroke::new(3.0, egui::Color32::GOLD)
+                } else {
+                    egui::Stroke::new(1.0, egui::Color32::GRAY)
+                };
+                let start = from.center_bottom();
+                let end = if target == id || to.center().y <= from.center().y {
+                    to.right_center()
+                } else {
+                    to.center_top()
+                };
+                painter.arrow(start, end - start, stroke);
+            }
+        }
+
+        for node in graph.nodes_iter() {
+            let id = node.id();
+            let Some(rect) = rect_of(id) else {
+                continue;
+            };
+
+            let fill = if runner.current_node_id == Some(id) {
+                egui::Color32::from_rgb(200, 160, 40)
+            } else if runner.has_visited(id) {
+                egui::Color32::from_rgb(50, 90, 140)
+            } else {
+                egui::Color32::from_rgb(60, 60, 60)
+            };
+            painter.rect_filled(rect, 4.0, fill);
+
+            let kind = match node {
+                DialogueNode::Text { .. } => "Text",
+                DialogueNode::Choice { .. } => "Choice",
+                DialogueNode::Random { .. } => "Random",
+                DialogueNode::Wait { .. } => "Wait",
+                DialogueNode::Input { .. } => "Input",
+                DialogueNode::Return { .. } => "Return",
+                DialogueNode::End { .. } => "End",
+                DialogueNode::Custom { node_type, .. } => node_type.as_str(),
+            };
+            painter.text(
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                format!("{:?} {}", id, kind),
+                egui::FontId::proportional(12.0),
+                egui::Color32::WHITE,
+            );
+
+            let node_response = ui
+                .interact(
+                    rect,
+                    ui.id().with(("dialogue_graph_node", id.0)),
+                    egui::Sense::click(),
+                )
+                .on_hover_text(format!("{}\n\nClick to jump here", node.display_name()));
+            if node_response.clicked() {
+                clicked = Some(id);
+            }
+        }
+    });
+
+    clicked
 }
@@ -0,0 +1,906 @@
+//! In-game visual editor for dialogue assets.
+
+use bevy::color::ColorToPacked;
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiPlugin};
+use std::path::PathBuf;
+
+use crate::{
+    asset::DialogueAsset,
+    graph::{
+        AdvanceTiming, ConnectionData, DialogueElement, DialogueGraph, DialogueNode, EntryPoint,
+        InputRule, NodeId, RandomMode, TextVariantMode,
+    },
+};
+
+/// Size of a node box in the editor canvas
+const NODE_SIZE: egui::Vec2 = egui::vec2(160.0, 48.0);
+/// Space kept around the nodes in the editor canvas
+const CANVAS_MARGIN: f32 = 200.0;
+
+/// Plugin adding the visual dialogue editor.
+///
+/// Press F2 to toggle the editor window. The editor works on a copy of the selected
+/// dialogue: "Apply" updates the loaded asset and "Save" also writes it back to its
+/// `*.dialogue.json` file, including the node positions.
+pub struct DialogueEditorPlugin;
+
+impl Plugin for DialogueEditorPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<EguiPlugin>() {
+            app.add_plugins(EguiPlugin);
+        }
+
+        app.init_resource::<DialogueEditorState>()
+            .add_systems(Update, dialogue_editor_system);
+
+        info!("Dialogue Editor enabled - press F2 to toggle");
+    }
+}
+
+/// Dialogue editor state
+#[derive(Resource)]
+pub struct DialogueEditorState {
+    /// Whether the editor is visible
+    pub visible: bool,
+    /// Directory that asset paths are relative to, used when saving
+    pub asset_root: PathBuf,
+    /// Dialogue being edited
+    dialogue: Option<AssetId<DialogueAsset>>,
+    /// Working copy of the edited graph
+    graph: Option<DialogueGraph>,
+    /// Node shown in the properties panel
+    selected_node: Option<NodeId>,
+    /// Source node of the connection being created
+    connecting_from: Option<NodeId>,
+    /// Tag being added to the selected node
+    new_tag: String,
+    /// Message shown in the toolbar
+    status: String,
+}
+
+impl Default for DialogueEditorState {
+    fn default() -> Self {
+        Self {
+            visible: false,
+            asset_root: PathBuf::from("assets"),
+            dialogue: None,
+            graph: None,
+            selected_node: None,
+            connecting_from: None,
+            new_tag: String::new(),
+            status: String::new(),
+        }
+    }
+}
+
+impl DialogueEditorState {
+    /// Opens a dialogue in the editor, discarding unapplied changes.
+    pub fn open(&mut self, dialogue: AssetId<DialogueAsset>, asset: &DialogueAsset) {
+        let mut graph = asset.graph.clone();
+        place_unpositioned_nodes(&mut graph);
+
+        self.dialogue = Some(dialogue);
+        self.graph = Some(graph);
+        self.selected_node = None;
+        self.connecting_from = None;
+        self.status.clear();
+    }
+}
+
+/// Gives a position to every node that doesn't have one yet.
+///
+/// Nodes are laid out on a grid in ID order, after the positioned nodes.
+fn place_unpositioned_nodes(graph: &mut DialogueGraph) {
+    let mut ids = graph.node_ids();
+    ids.sort_by_key(|id| id.0);
+
+    let mut slot = 0;
+    for id in ids {
+        let Some(metadata) = graph.node_metadata_mut(id) else {
+            continue;
+        };
+        if metadata.position.is_none() {
+            let (row, column) = (slot / 4, slot % 4);
+            metadata.position = Some(Vec2::new(
+                20.0 + column as f32 * (NODE_SIZE.x + 60.0),
+                20.0 + row as f32 * (NODE_SIZE.y + 60.0),
+            ));
+            slot += 1;
+        }
+    }
+}
+
+/// Gets the position of a node in the editor canvas.
+fn node_position(graph: &DialogueGraph, id: NodeId) -> egui::Vec2 {
+    graph
+        .node_metadata(id)
+        .and_then(|metadata| metadata.position)
+        .map_or(egui::Vec2::ZERO, |position| {
+            egui::vec2(position.x, position.y)
+        })
+}
+
+/// Gets the color set on a node in the editor, if any.
+fn node_color(graph: &DialogueGraph, id: NodeId) -> Option<egui::Color32> {
+    let hex = graph.node_metadata(id)?.color.as_deref()?;
+    let [r, g, b, a] = Srgba::hex(hex).ok()?.to_u8_array();
+    Some(egui::Color32::from_rgba_unmultiplied(r, g, b, a))
+}
+
+/// Edits an optional string, an empty string meaning None.
+fn optional_text_edit(ui: &mut egui::Ui, label: &str, value: &mut Option<String>) -> bool {
+    let mut text = value.clone().unwrap_or_default();
+    let changed = ui
+        .horizontal(|ui| {
+            ui.label(label);
+            ui.text_edit_singleline(&mut text).changed()
+        })
+        .inner;
+
+    if changed {
+        *value = (!text.is_empty()).then_some(text);
+    }
+    changed
+}
+
+/// System that displays the dialogue editor.
+fn dialogue_editor_system(
+    mut state: ResMut<DialogueEditorState>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    asset_server: Res<AssetServer>,
+    mut dialogue_assets: ResMut<Assets<DialogueAsset>>,
+    mut contexts: EguiContexts,
+) {
+    // Toggle the editor with F2
+    if keyboard_input.just_pressed(KeyCode::F2) {
+        state.visible = !state.visible;
+    }
+
+    if !state.visible {
+        return;
+    }
+
+    let ctx = contexts.ctx_mut().clone();
+    let mut visible = true;
+
+    egui::Window::new("Dialogue Editor")
+        .open(&mut visible)
+        .default_size([900.0, 600.0])
+        .show(&ctx, |ui| {
+            toolbar(ui, &mut state, &asset_server, &mut dialogue_assets);
+            ui.separator();
+
+            let DialogueEditorState {
+                graph,
+                selected_node,
+                connecting_from,
+                new_tag,
+                status,
+                ..
+            } = &mut *state;
+            let Some(graph) = graph else {
+                ui.label("Select a dialogue to edit");
+                return;
+            };
+
+            egui::SidePanel::right("dialogue_editor_properties")
+                .resizable(true)
+                .default_width(280.0)
+                .show_inside(ui, |ui| {
+                    properties_panel(ui, graph, selected_node, connecting_from, new_tag, status);
+                });
+
+            egui::CentralPanel::default().show_inside(ui, |ui| {
+                canvas(ui, graph, selected_node, connecting_from, status);
+            });
+        });
+
+    state.visible = visible;
+}
+
+/// Draws the dialogue picker and the editor actions.
+fn toolbar(
+    ui: &mut egui::Ui,
+    state: &mut DialogueEditorState,
+    asset_server: &AssetServer,
+    dialogue_assets: &mut Assets<DialogueAsset>,
+) {
+    let label = |id: AssetId<DialogueAsset>| {
+        asset_server
+            .get_path(id)
+            .map_or_else(|| format!("{:?}", id), |path| path.to_string())
+    };
+
+    ui.horizontal(|ui| {
+        let mut selected = state.dialogue;
+        egui::ComboBox::from_label("Dialogue")
+            .selected_text(selected.map(label).unwrap_or_default())
+            .show_ui(ui, |ui| {
+                for (id, _) in dialogue_assets.iter() {
+                    ui.selectable_value(&mut selected, Some(id), label(id));
+                }
+            });
+        if selected != state.dialogue {
+            if let Some(id) = selected {
+                if let Some(asset) = dialogue_assets.get(id) {
+                    state.open(id, asset);
+                }
+            }
+        }
+
+        let (Some(id), Some(graph)) = (state.dialogue, state.graph.as_mut()) else {
+            return;
+        };
+
+        ui.separator();
+        if ui.button("Add Text").clicked() {
+            let node_id = add_node(graph, |id| DialogueNode::text(id, "New text"));
+            state.selected_node = Some(node_id);
+        }
+        if ui.button("Add Choice").clicked() {
+            let node_id = add_node(graph, DialogueNode::choice);
+            state.selected_node = Some(node_id);
+        }
+        if ui.button("Add Random").clicked() {
+            let node_id = add_node(graph, |id| DialogueNode::random(id, RandomMode::default()));
+            state.selected_node = Some(node_id);
+        }
+        if ui.button("Add Wait").clicked() {
+            let node_id = add_node(graph, |id| DialogueNode::wait(id, 1.0));
+            state.selected_node = Some(node_id);
+        }
+        if ui.button("Add Input").clicked() {
+            let node_id = add_node(graph, |id| DialogueNode::input(id, "input"));
+            state.selected_node = Some(node_id);
+        }
+        if ui.button("Add Return").clicked() {
+            let node_id = add_node(graph, DialogueNode::return_to_hub);
+            state.selected_node = Some(node_id);
+        }
+        if ui.button("Add End").clicked() {
+            let node_id = add_node(graph, DialogueNode::end);
+            state.selected_node = Some(node_id);
+        }
+
+        ui.separator();
+        if ui.button("Revert").clicked() {
+            if let Some(asset) = dialogue_assets.get(id) {
+                state.open(id, asset);
+            }
+            return;
+        }
+        if ui.button("Apply").clicked() {
+            state.status = match apply(id, graph, dialogue_assets) {
+                Ok(()) => "Applied".to_string(),
+                Err(err) => err,
+            };
+        }
+        if ui.button("Save").clicked() {
+            let result = apply(id, graph, dialogue_assets).and_then(|()| {
+                let path = asset_server
+                    .get_path(id)
+                    .ok_or_else(|| "Dialogue has no asset path".to_string())?;
+                let file = state.asset_root.join(path.path());
+                let asset = dialogue_assets
+                    .get(id)
+                    .ok_or_else(|| "Dialogue is no longer loaded".to_string())?;
+                let json = serde_json::to_string_pretty(asset).map_err(|err| err.to_string())?;
+                std::fs::write(&file, json)
+                    .map_err(|err| format!("Failed to write {}: {}", file.display(), err))?;
+                Ok(file)
+            });
+
+            state.status = match result {
+                Ok(file) => match graph.validate() {
+                    Ok(()) => format!("Saved {}", file.display()),
+                    Err(err) => format!("Saved {} with warning: {}", file.display(), err),
+                },
+                Err(err) => err,
+            };
+        }
+
+        ui.label(state.status.as_str());
+    });
+}
+
+/// Adds a node with the next free ID and returns that ID.
+fn add_node(graph: &mut DialogueGraph, create: impl FnOnce(NodeId) -> DialogueNode) -> NodeId {
+    let id = NodeId(
+        graph
+            .node_ids()
+            .iter()
+            .map(|id| id.0 + 1)
+            .max()
+            .unwrap_or(1),
+    );
+    graph.add_node(create(id));
+    place_unpositioned_nodes(graph);
+    id
+}
+
+/// Deletes a node, along with the entry points starting at it.
+///
+/// The start node can't be deleted, another node has to be set as start first.
+fn delete_node(graph: &mut DialogueGraph, id: NodeId) -> Result<(), String> {
+    if graph.start_node == id {
+        return Err("Set another start node before deleting this one".to_string());
+    }
+    graph.remove_node(id).map_err(|err| err.to_string())?;
+    graph
+        .entry_points
+        .retain(|entry_point| entry_point.node_id != id);
+    Ok(())
+}
+
+/// Copies the working graph into the loaded asset.
+fn apply(
+    id: AssetId<DialogueAsset>,
+    graph: &DialogueGraph,
+    dialogue_assets: &mut Assets<DialogueAsset>,
+) -> Result<(), String> {
+    let asset = dialogue_assets
+        .get_mut(id)
+        .ok_or_else(|| "Dialogue is no longer loaded".to_string())?;
+    asset.graph = graph.clone();
+    asset.name = graph.name.clone();
+    Ok(())
+}
+
+/// Draws the nodes and connections, and handles selecting, moving and connecting nodes.
+fn canvas(
+    ui: &mut egui::Ui,
+    graph: &mut DialogueGraph,
+    selected_node: &mut Option<NodeId>,
+    connecting_from: &mut Option<NodeId>,
+    status: &mut String,
+) {
+    let mut ids = graph.node_ids();
+    ids.sort_by_key(|id| id.0);
+
+    egui::ScrollArea::both().show(ui, |ui| {
+        let extent = ids
+            .iter()
+            .map(|&id| node_position(graph, id) + NODE_SIZE)
+            .fold(egui::Vec2::ZERO, |extent, corner| extent.max(corner));
+        let size = (extent + egui::Vec2::splat(CANVAS_MARGIN)).max(ui.available_size());
+        let (response, painter) = ui.allocate_painter(size, egui::Sense::hover());
+        let origin = response.rect.min;
+
+        let rect_of = |graph: &DialogueGraph, id: NodeId| {
+            egui::Rect::from_min_size(origin + node_position(graph, id), NODE_SIZE)
+        };
+
+        // Draw connections first so nodes are drawn on top
+        for &id in &ids {
+            let from = rect_of(graph, id);
+            for (target, data) in graph.get_connections(id) {
+                let to = rect_of(graph, target);
+                let start = from.center_bottom();
+                let end = to.center_top();
+                painter.arrow(
+                    start,
+                    end - start,
+                    egui::Stroke::new(1.5, egui::Color32::GRAY),
+                );
+
+                if let Some(label) = &data.label {
+                    painter.text(
+                        start + (end - start) / 2.0,
+                        egui::Align2::CENTER_CENTER,
+                        label,
+                        egui::FontId::proportional(11.0),
+                        egui::Color32::LIGHT_GRAY,
+                    );
+                }
+            }
+        }
+
+        for id in ids {
+            let rect = rect_of(graph, id);
+            let node_response = ui.interact(
+                rect,
+                ui.id().with(("dialogue_editor_node", id.0)),
+                egui::Sense::click_and_drag(),
+            );
+
+            if node_response.dragged() {
+                if let Some(metadata) = graph.node_metadata_mut(id) {
+                    let delta = node_response.drag_delta();
+                    let position =
+                        metadata.position.unwrap_or_default() + Vec2::new(delta.x, delta.y);
+                    metadata.position = Some(position.max(Vec2::ZERO));
+                }
+            }
+
+            if node_response.clicked() {
+                match connecting_from.take() {
+                    Some(from) => {
+                        *status = match graph.connect(from, id, ConnectionData::new(None)) {
+                            Ok(()) => format!("Connected {:?} to {:?}", from, id),
+                            Err(err) => err.to_string(),
+                        };
+                    }
+                    None => *selected_node = Some(id),
+                }
+            }
+
+            let Some(node) = graph.get_node(id) else {
+                continue;
+            };
+
+            if *selected_node == Some(id) {
+                painter.rect_filled(rect.expand(3.0), 6.0, egui::Color32::from_rgb(200, 160, 40));
+            }
+            let fill = node_color(graph, id).unwrap_or(match node {
+                DialogueNode::Text { .. } => egui::Color32::from_rgb(50, 70, 100),
+                DialogueNode::Choice { .. } => egui::Color32::from_rgb(90, 60, 100),
+                DialogueNode::Random { .. } => egui::Color32::from_rgb(110, 85, 45),
+                DialogueNode::Wait { .. } => egui::Color32::from_rgb(80, 80, 80),
+                DialogueNode::Input { .. } => egui::Color32::from_rgb(100, 95, 45),
+                DialogueNode::Return { .. } => egui::Color32::from_rgb(110, 55, 55),
+                DialogueNode::End { .. } => egui::Color32::from_rgb(40, 40, 40),
+                DialogueNode::Custom { .. } => egui::Color32::from_rgb(60, 95, 60),
+            });
+            painter.rect_filled(rect, 4.0, fill);
+
+            let start_marker = if graph.start_node == id { "▶ " } else { "" };
+            let mut summary: String = node.display_name().chars().take(24).collect();
+            if summary.len() < node.display_name().len() {
+                summary.push('…');
+            }
+            painter.text(
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                format!("{}{:?}\n{}", start_marker, id, summary),
+                egui::FontId::proportional(12.0),
+                egui::Color32::WHITE,
+            );
+        }
+    });
+}
+
+/// Draws the properties of the graph and of the selected node.
+fn properties_panel(
+    ui: &mut egui::Ui,
+    graph: &mut DialogueGraph,
+    selected_node: &mut Option<NodeId>,
+    connecting_from: &mut Option<NodeId>,
+    new_tag: &mut String,
+    status: &mut String,
+) {
+    ui.heading("Dialogue");
+    optional_text_edit(ui, "Name", &mut graph.name);
+    ui.label(format!("Start node: {:?}", graph.start_node));
+
+    let mut removed_entry_point = None;
+    for (index, entry_point) in graph.entry_points.iter_mut().enumerate() {
+        ui.push_id(("entry_point", index), |ui| {
+            ui.horizontal(|ui| {
+                ui.label(format!("Entry {:?}:", entry_point.node_id));
+                ui.text_edit_singleline(&mut entry_point.name);
+                if ui.small_button("x").clicked() {
+                    removed_entry_point = Some(index);
+                }
+            });
+            optional_text_edit(ui, "Condition", &mut entry_point.condition);
+        });
+    }
+    if let Some(index) = removed_entry_point {
+        graph.entry_points.remove(index);
+    }
+
+    ui.separator();
+
+    let Some(id) = *selected_node else {
+        ui.label("Click a node to edit it");
+        return;
+    };
+    let Some(mut node) = graph.get_node(id).cloned() else {
+        *selected_node = None;
+        return;
+    };
+
+    ui.heading(format!("Node {:?}", id));
+
+    // Edit the node content on a copy, then write it back
+    let mut changed = false;
+    match &mut node {
+        DialogueNode::Text {
+            text,
+            variants,
+            variant_mode,
+            advance,
+            speaker,
+            portrait,
+            ..
+        } => {
+            ui.label("Text");
+            changed |= ui.text_edit_multiline(text).changed();
+
+            ui.label("Variants");
+            let mut removed = None;
+            for (index, variant) in variants.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    changed |= ui.text_edit_multiline(variant).changed();
+                    if ui.small_button("✕").clicked() {
+                        removed = Some(index);
+                    }
+                });
+            }
+            if let Some(index) = removed {
+                variants.remove(index);
+                changed = true;
+            }
+            ui.horizontal(|ui| {
+                if ui.button("Add variant").clicked() {
+                    variants.push(String::new());
+                    changed = true;
+                }
+                egui::ComboBox::from_label("Mode")
+                    .selected_text(format!("{:?}", variant_mode))
+                    .show_ui(ui, |ui| {
+                        for option in [
+                            TextVariantMode::Sequence,
+                            TextVariantMode::Cycle,
+                            TextVariantMode::Shuffle,
+                            TextVariantMode::Once,
+                        ] {
+                            changed |= ui
+                                .selectable_value(variant_mode, option, format!("{:?}", option))
+                                .changed();
+                        }
+                    });
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Advance");
+                let delay = match advance {
+                    AdvanceTiming::After(delay) => *delay,
+                    _ => 2.0,
+                };
+                for (option, label) in [
+                    (AdvanceTiming::Runner, "Runner"),
+                    (AdvanceTiming::After(delay), "After"),
+                    (AdvanceTiming::WaitForSignal, "Signal"),
+                ] {
+                    changed |= ui.selectable_value(advance, option, label).changed();
+                }
+                if let AdvanceTiming::After(delay) = advance {
+                    changed |= ui
+                        .add(
+                            egui::DragValue::new(delay)
+                                .speed(0.1)
+                                .range(0.0..=f32::MAX)
+                                .suffix("s"),
+                        )
+                        .changed();
+                }
+            });
+
+            changed |= optional_text_edit(ui, "Speaker", speaker);
+            changed |= optional_text_edit(ui, "Portrait", portrait);
+        }
+        DialogueNode::Choice {
+            prompt,
+            timeout,
+            default_choice,
+            hub,
+            speaker,
+            portrait,
+            ..
+        } => {
+            changed |= optional_text_edit(ui, "Prompt", prompt);
+            changed |= ui
+                .checkbox(hub, "Hub")
+                .on_hover_text("Return nodes lead back to the last hub entered")
+                .changed();
+
+            ui.horizontal(|ui| {
+                let mut timed = timeout.is_some();
+                if ui.checkbox(&mut timed, "Timeout").changed() {
+                    *timeout = timed.then_some(5.0);
+                    changed = true;
+                }
+                if let Some(seconds) = timeout {
+                    changed |= ui
+                        .add(
+                            egui::DragValue::new(seconds)
+                                .speed(0.1)
+                                .range(0.0..=f32::MAX)
+                                .suffix("s"),
+                        )
+                        .changed();
+                    ui.label("Default");
+                    changed |= ui.add(egui::DragValue::new(default_choice)).changed();
+                }
+            });
+
+            changed |= optional_text_edit(ui, "Speaker", speaker);
+            changed |= optional_text_edit(ui, "Portrait", portrait);
+        }
+        DialogueNode::Random { mode, .. } => {
+            egui::ComboBox::from_label("Mode")
+                .selected_text(format!("{:?}", mode))
+                .show_ui(ui, |ui| {
+                    for option in [RandomMode::Weighted, RandomMode::Shuffle, RandomMode::Cycle] {
+                        changed |= ui
+                            .selectable_value(mode, option, format!("{:?}", option))
+                            .changed();
+                    }
+                });
+        }
+        DialogueNode::Wait { duration, .. } => {
+            ui.horizontal(|ui| {
+                ui.label("Duration");
+                changed |= ui
+                    .add(
+                        egui::DragValue::new(duration)
+                            .speed(0.1)
+                            .range(0.0..=f32::MAX)
+                            .suffix("s"),
+                    )
+                    .changed();
+            });
+        }
+        DialogueNode::Input {
+            prompt,
+            variable,
+            rules,
+            speaker,
+            portrait,
+            ..
+        } => {
+            changed |= optional_text_edit(ui, "Prompt", prompt);
+            ui.horizontal(|ui| {
+                ui.label("Variable");
+                changed |= ui.text_edit_singleline(variable).changed();
+            });
+
+            ui.label("Rules");
+            let mut removed = None;
+            for (index, rule) in rules.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.label(format!("Input {}", rule));
+                    match rule {
+                        InputRule::MinLength(length) | InputRule::MaxLength(length) => {
+                            changed |= ui.add(egui::DragValue::new(length)).changed();
+                        }
+                        InputRule::OneOf(values) => {
+                            let mut text = values.join(", ");
+                            if ui.text_edit_singleline(&mut text).changed() {
+                                *values = text
+                                    .split(',')
+                                    .map(|value| value.trim().to_string())
+                                    .filter(|value| !value.is_empty())
+                                    .collect();
+                                changed = true;
+                            }
+                        }
+                        _ => {}
+                    }
+                    if ui.small_button("✕").clicked() {
+                        removed = Some(index);
+                    }
+                });
+            }
+            if let Some(index) = removed {
+                rules.remove(index);
+                changed = true;
+            }
+            ui.horizontal_wrapped(|ui| {
+                for (option, label) in [
+                    (InputRule::NotEmpty, "Not empty"),
+                    (InputRule::MinLength(1), "Min length"),
+                    (InputRule::MaxLength(16), "Max length"),
+                    (InputRule::Alphanumeric, "Alphanumeric"),
+                    (InputRule::Numeric, "Numeric"),
+                    (InputRule::OneOf(Vec::new()), "One of"),
+                ] {
+                    if ui.small_button(format!("+ {}", label)).clicked() {
+                        rules.push(option);
+                        changed = true;
+                    }
+                }
+            });
+
+            changed |= optional_text_edit(ui, "Speaker", speaker);
+            changed |= optional_text_edit(ui, "Portrait", portrait);
+        }
+        DialogueNode::Return { .. } => {
+            ui.label("Goes back to the last hub choice node entered");
+        }
+        DialogueNode::End { outcome, .. } => {
+            changed |= optional_text_edit(ui, "Outcome", outcome);
+        }
+        DialogueNode::Custom {
+            node_type, data, ..
+        } => {
+            ui.horizontal(|ui| {
+                ui.label("Type");
+                changed |= ui.text_edit_singleline(node_type).changed();
+            });
+            ui.label("Data (edit in the dialogue file)");
+            ui.code(serde_json::to_string_pretty(data).unwrap_or_default());
+        }
+    }
+    if changed {
+        if let Err(err) = graph.update_node(id, node) {
+            *status = err.to_string();
+        }
+    }
+
+    // Editor metadata
+    if let Some(metadata) = graph.node_metadata_mut(id) {
+        optional_text_edit(ui, "Color", &mut metadata.color);
+
+        ui.label("Comment");
+        let mut comment = metadata.comment.clone().unwrap_or_default();
+        if ui.text_edit_multiline(&mut comment).changed() {
+            metadata.comment = (!comment.is_empty()).then_some(comment);
+        }
+
+        ui.label("Tags");
+        let mut removed = None;
+        ui.horizontal_wrapped(|ui| {
+            for tag in &metadata.tags {
+                if ui.small_button(format!("{} ✕", tag)).clicked() {
+                    removed = Some(tag.clone());
+                }
+            }
+        });
+        if let Some(tag) = removed {
+            metadata.remove_tag(&tag);
+        }
+        ui.horizontal(|ui| {
+            ui.text_edit_singleline(new_tag);
+            if ui.button("Add tag").clicked() && !new_tag.trim().is_empty() {
+                metadata.add_tag(new_tag.trim());
+                new_tag.clear();
+            }
+        });
+    }
+
+    ui.horizontal(|ui| {
+        if ui.button("Set as start").clicked() {
+            graph.start_node = id;
+        }
+        if ui.button("Add entry point").clicked() {
+            graph.add_entry_point(EntryPoint::new(format!("entry_{}", id.0), id));
+        }
+        if ui.button("Delete").clicked() {
+            match delete_node(graph, id) {
+                Ok(()) => *selected_node = None,
+                Err(err) => *status = err,
+            }
+        }
+    });
+
+    if selected_node.is_none() {
+        return;
+    }
+
+    ui.separator();
+    ui.label("Connections");
+
+    let is_random = matches!(graph.get_node(id), Some(DialogueNode::Random { .. }));
+    let is_input = matches!(graph.get_node(id), Some(DialogueNode::Input { .. }));
+    let is_choice = matches!(graph.get_node(id), Some(DialogueNode::Choice { .. }));
+    let targets: Vec<_> = graph
+        .get_connections(id)
+        .into_iter()
+        .map(|(target, _)| target)
+        .collect();
+    let mut removed = None;
+    // Connections are edited by position, several of them can lead to the same node
+    for (index, target) in targets.into_iter().enumerate() {
+        ui.horizontal(|ui| {
+            ui.label(format!("→ {:?}", target));
+            if let Some(data) = graph.get_connection_at_mut(id, index) {
+                let mut label = data.label.clone().unwrap_or_default();
+                if ui.text_edit_singleline(&mut label).changed() {
+                    data.label = (!label.is_empty()).then_some(label);
+                }
+                if is_random {
+                    ui.add(
+                        egui::DragValue::new(&mut data.weight)
+                            .speed(0.1)
+                            .range(0.0..=f32::MAX),
+                    )
+                    .on_hover_text("Weight");
+                }
+                if is_choice {
+                    ui.checkbox(&mut data.once, "Once")
+                        .on_hover_text("Remove this choice after it has been taken");
+                }
+                if is_random || is_input {
+                    let mut condition = data.condition.clone().unwrap_or_default();
+                    if ui
+                        .text_edit_singleline(&mut condition)
+                        .on_hover_text("Condition")
+                        .changed()
+                    {
+                        data.condition = (!condition.is_empty()).then_some(condition);
+                    }
+                }
+            }
+            if ui.small_button("Remove").clicked() {
+                removed = Some(index);
+            }
+        });
+    }
+    if let Some(index) = removed {
+        graph.remove_connection_at(id, index);
+    }
+
+    if ui.button("Connect to...").clicked() {
+        *connecting_from = Some(id);
+        *status = "Click the node to connect to".to_string();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(graph: &DialogueGraph, id: NodeId) -> Option<Vec2> {
+        graph
+            .node_metadata(id)
+            .and_then(|metadata| metadata.position)
+    }
+
+    #[test]
+    fn test_place_unpositioned_nodes() {
+        let mut graph = DialogueGraph::new(NodeId(1));
+        for id in 1..=6 {
+            graph.add_node(DialogueNode::text(NodeId(id), "Hello"));
+        }
+        graph.node_metadata_mut(NodeId(3)).unwrap().position = Some(Vec2::new(500.0, 500.0));
+
+        place_unpositioned_nodes(&mut graph);
+
+        // Positioned nodes are kept, the others fill the grid in ID order
+        assert_eq!(position(&graph, NodeId(3)), Some(Vec2::new(500.0, 500.0)));
+        assert_eq!(position(&graph, NodeId(1)), Some(Vec2::new(20.0, 20.0)));
+        assert_eq!(position(&graph, NodeId(2)), Some(Vec2::new(240.0, 20.0)));
+        assert_eq!(position(&graph, NodeId(4)), Some(Vec2::new(460.0, 20.0)));
+        assert_eq!(position(&graph, NodeId(5)), Some(Vec2::new(680.0, 20.0)));
+        assert_eq!(position(&graph, NodeId(6)), Some(Vec2::new(20.0, 128.0)));
+    }
+
+    #[test]
+    fn test_add_node() {
+        let mut graph = DialogueGraph::new(NodeId(1));
+        assert_eq!(add_node(&mut graph, DialogueNode::end), NodeId(1));
+
+        graph.add_node(DialogueNode::text(NodeId(7), "Hello"));
+        let id = add_node(&mut graph, |id| DialogueNode::text(id, "New"));
+        assert_eq!(id, NodeId(8));
+        assert!(matches!(
+            graph.get_node(id),
+            Some(DialogueNode::Text { text, .. }) if text == "New"
+        ));
+        assert!(position(&graph, id).is_some());
+        assert!(position(&graph, NodeId(7)).is_some());
+    }
+
+    #[test]
+    fn test_delete_node() {
+        let mut graph = DialogueGraph::new(NodeId(1))
+            .with_node(DialogueNode::text(NodeId(1), "Hello"))
+            .with_node(DialogueNode::text(NodeId(2), "Welcome back"))
+            .with_entry_point(EntryPoint::new("return", NodeId(2)))
+            .with_entry_point(EntryPoint::new("start", NodeId(1)));
+
+        assert!(delete_node(&mut graph, NodeId(1)).is_err());
+        assert!(graph.contains_node(NodeId(1)));
+
+        delete_node(&mut graph, NodeId(2)).unwrap();
+        assert!(!graph.contains_node(NodeId(2)));
+        assert!(graph.entry_point("return").is_none());
+        assert!(graph.entry_point("start").is_some());
+        assert!(graph.validate().is_ok());
+
+        assert!(delete_node(&mut graph, NodeId(99)).is_err());
+    }
+}
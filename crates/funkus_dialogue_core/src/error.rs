@@ -4,7 +4,8 @@
 //! It provides structured error handling for dialogue operations, making it easier to
 //! identify and handle specific error conditions.
 
-use crate::graph::NodeId;
+use crate::graph::{InputRule, NodeId};
+use crate::runtime::DialogueState;
 use bevy::prelude::*;
 use thiserror::Error;
 
@@ -35,8 +36,8 @@ use thiserror::Error;
 ///                     funkus_dialogue::error::DialogueError::NodeNotFound(id) => {
 ///                         println!("Node {:?} not found", id)
 ///                     },
-///                     funkus_dialogue::error::DialogueError::GraphError(msg) => {
-///                         println!("Graph structure error: {}", msg)
+///                     funkus_dialogue::error::DialogueError::GraphError(err) => {
+///                         println!("Graph structure error: {}", err)
 ///                     },
 ///                     funkus_dialogue::error::DialogueError::AssetNotLoaded => {
 ///                         println!("Dialogue asset hasn't been loaded yet")
@@ -77,15 +78,182 @@ pub enum DialogueError {
 
     /// Invalid state transition
     #[error("Invalid state transition: from {from:?} with action {action}")]
-    InvalidStateTransition { from: String, action: String },
+    InvalidStateTransition {
+        /// State the runner was in
+        from: DialogueState,
+        /// Action that was attempted
+        action: DialogueAction,
+    },
 
     /// General graph error
     #[error("Graph error: {0}")]
-    GraphError(String),
+    GraphError(#[from] GraphError),
 
     /// Asset not loaded
     #[error("Dialogue asset not loaded")]
     AssetNotLoaded,
+
+    /// A timed choice ran out of time without any choice to take
+    #[error("Choice node {0:?} has no choices to take")]
+    NoChoices(NodeId),
+
+    /// The entity a dialogue was started on doesn't exist anymore
+    #[error("Entity {0:?} not found")]
+    EntityNotFound(Entity),
+
+    /// Nodes the runner passes through, such as random nodes, kept leading to each other
+    #[error("Passed-through nodes loop without reaching another node, last at {0:?}")]
+    PassThroughLoop(NodeId),
+
+    /// No custom node type is registered under the type of a node
+    #[error("Unknown node type '{node_type}' for node {node_id:?}")]
+    UnknownNodeType {
+        /// ID of the node
+        node_id: NodeId,
+        /// Type of the node
+        node_type: String,
+    },
+
+    /// The data of a custom node doesn't match its registered type
+    #[error("Invalid data for node {node_id:?}: {message}")]
+    InvalidNodeData {
+        /// ID of the node
+        node_id: NodeId,
+        /// Why the data couldn't be deserialized
+        message: String,
+    },
+
+    /// The input submitted to an input node breaks one of its rules
+    #[error("Invalid input for node {node_id:?}: input {rule}")]
+    InvalidInput {
+        /// ID of the input node
+        node_id: NodeId,
+        /// The rule that was broken
+        rule: InputRule,
+    },
+
+    /// The dialogue has no entry point with this name
+    #[error("Entry point '{0}' not found")]
+    UnknownEntryPoint(String),
+}
+
+/// Actions that change the state of a dialogue runner.
+///
+/// Used by [`DialogueError::InvalidStateTransition`] to report which action failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum DialogueAction {
+    /// Advancing to the next node
+    Advance,
+    /// Selecting a choice
+    SelectChoice,
+    /// Submitting the input of an input node
+    SubmitInput,
+}
+
+impl std::fmt::Display for DialogueAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DialogueAction::Advance => write!(f, "advance"),
+            DialogueAction::SelectChoice => write!(f, "select_choice"),
+            DialogueAction::SubmitInput => write!(f, "submit_input"),
+        }
+    }
+}
+
+/// Errors in the structure of a dialogue graph.
+///
+/// Returned by the [`DialogueGraph`](crate::graph::DialogueGraph) methods that modify
+/// or validate the graph.
+///
+/// # Example
+///
+/// ```rust
+/// use funkus_dialogue_core::error::GraphError;
+/// use funkus_dialogue_core::graph::{DialogueGraph, NodeId};
+///
+/// let graph = DialogueGraph::new(NodeId(1));
+/// assert_eq!(graph.validate(), Err(GraphError::MissingStartNode(NodeId(1))));
+/// ```
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum GraphError {
+    /// Node not found in the graph
+    #[error("Node {0:?} not found")]
+    NodeNotFound(NodeId),
+
+    /// The start node doesn't exist
+    #[error("Start node {0:?} does not exist")]
+    MissingStartNode(NodeId),
+
+    /// Node that can't be reached from the start node or any entry point
+    #[error("Node {0:?} is unreachable from start node")]
+    UnreachableNode(NodeId),
+
+    /// The node of an entry point doesn't exist
+    #[error("Entry point '{name}' starts at missing node {node_id:?}")]
+    MissingEntryNode {
+        /// Name of the entry point
+        name: String,
+        /// The missing node
+        node_id: NodeId,
+    },
+
+    /// The default choice of a timed choice node isn't one of its choices
+    #[error("Default choice {default_choice} of {node_id:?} is out of range ({choices} choices)")]
+    InvalidDefaultChoice {
+        /// The timed choice node
+        node_id: NodeId,
+        /// Index of the default choice
+        default_choice: usize,
+        /// Number of choices of the node
+        choices: usize,
+    },
+
+    /// No connection between two nodes
+    #[error("No connection from {from:?} to {to:?}")]
+    ConnectionNotFound {
+        /// Source node of the missing connection
+        from: NodeId,
+        /// Target node of the missing connection
+        to: NodeId,
+    },
+
+    /// The node ID to graph index mapping is out of sync with the graph
+    #[error("Internal error: node index mapping is inconsistent")]
+    InconsistentMapping,
+}
+
+/// Speakers of a dialogue that are missing from a character database.
+///
+/// Returned by [`CharacterDatabase::validate_speakers`](crate::CharacterDatabase::validate_speakers).
+///
+/// # Example
+///
+/// ```rust
+/// use funkus_dialogue_core::UnknownSpeakersError;
+/// use funkus_dialogue_core::graph::NodeId;
+///
+/// let error = UnknownSpeakersError {
+///     speakers: vec![(NodeId(2), "stranger".to_string())],
+/// };
+/// assert_eq!(
+///     error.to_string(),
+///     "Unknown speakers in character database: NodeId(2) ('stranger')"
+/// );
+/// ```
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Unknown speakers in character database: {}", list_speakers(.speakers))]
+pub struct UnknownSpeakersError {
+    /// Nodes with a speaker missing from the database and that speaker ID, ordered by node ID
+    pub speakers: Vec<(NodeId, String)>,
+}
+
+/// Formats the nodes and speakers of an [`UnknownSpeakersError`].
+fn list_speakers(speakers: &[(NodeId, String)]) -> String {
+    speakers
+        .iter()
+        .map(|(node_id, speaker)| format!("{:?} ('{}')", node_id, speaker))
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 /// Result type for dialogue operations
@@ -97,13 +265,14 @@ pub enum DialogueError {
 ///
 /// ```rust
 /// use funkus_dialogue::error::{DialogueResult, DialogueError};
+/// use funkus_dialogue::graph::NodeId;
 ///
 /// fn some_dialogue_operation() -> DialogueResult<String> {
 ///     // An operation that could fail
 ///     if condition_is_met() {
 ///         Ok("Operation succeeded".to_string())
 ///     } else {
-///         Err(DialogueError::GraphError("Something went wrong".to_string()))
+///         Err(DialogueError::NodeNotFound(NodeId(1)))
 ///     }
 /// }
 /// ```
@@ -12,12 +12,23 @@
 //!    - `StopDialogue` - Stop a dialogue
 //!    - `AdvanceDialogue` - Move to the next node
 //!    - `SelectDialogueChoice` - Select a choice (without advancing)
+//!    - `SubmitDialogueInput` - Submit the player's input to an input node
+//!    - `JumpDialogue` - Move a dialogue to any node
 //!
 //! 2. **Notification Events** - Sent by the dialogue system to notify about state changes:
 //!    - `DialogueStarted` - A dialogue has started
+//!    - `DialogueStartFailed` - A dialogue couldn't start because its asset failed to load
 //!    - `DialogueEnded` - A dialogue has ended
 //!    - `DialogueNodeActivated` - A node has been activated
 //!    - `DialogueChoiceMade` - A choice has been selected (sent upon selection, before advancing)
+//!    - `DialogueChoiceTimedOut` - A timed choice ran out of time and moved on by itself
+//!    - `DialogueReloadWarning` - A running dialogue lost its node in a hot reload
+//!    - `DialogueErrorOccurred` - An action on a dialogue failed
+//!
+//! Notification events that concern a single entity (`DialogueStarted`, `DialogueEnded`,
+//! `DialogueNodeActivated`, `DialogueChoiceMade`, `DialogueChoiceTimedOut` and
+//! `DialogueErrorOccurred`) are also triggered on that entity, so observers can react to the dialogue of a specific NPC.
+//! `StartDialogue` and `AdvanceDialogue` can be triggered as well as sent.
 //!
 //! ## Usage Example
 //!
@@ -47,6 +58,7 @@
 //!             start_events.send(StartDialogue {
 //!                 entity: player,
 //!                 dialogue_handle: Handle::default(), // Use actual handle
+//!                 entry_point: None,
 //!             });
 //!         }
 //!     }
@@ -62,9 +74,13 @@
 //! }
 //! ```
 
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 
-use crate::graph::NodeId;
+use crate::asset::{CharacterDatabase, DialogueAsset, DialogueCharacters};
+use crate::error::DialogueError;
+use crate::graph::{DialogueNodeKind, NodeId};
+use crate::runtime::DialogueRunner;
 
 /// Event sent when a dialogue starts.
 ///
@@ -75,6 +91,7 @@ use crate::graph::NodeId;
 ///
 /// * `entity` - Entity running the dialogue
 /// * `start_node_id` - ID of the start node
+/// * `entry_point` - Name of the entry point the dialogue started from, if any
 ///
 /// # Example
 ///
@@ -95,6 +112,8 @@ pub struct DialogueStarted {
     pub entity: Entity,
     /// ID of the start node
     pub start_node_id: NodeId,
+    /// Name of the entry point the dialogue started from, if any
+    pub entry_point: Option<String>,
 }
 
 /// Event sent when a dialogue node is activated.
@@ -103,10 +122,19 @@ pub struct DialogueStarted {
 /// It can be used to track dialogue progress or trigger game events
 /// based on specific nodes.
 ///
+/// The event carries what the node shows, so audio, subtitle or analytics systems
+/// can react to it without looking up the dialogue asset.
+///
 /// # Fields
 ///
 /// * `entity` - Entity running the dialogue
 /// * `node_id` - ID of the activated node
+/// * `kind` - Kind of the activated node
+/// * `text` - Text of a text node, or prompt of a choice node
+/// * `speaker` - Speaker ID of the node
+/// * `portrait` - Portrait identifier of the node, or the default portrait of its speaker
+/// * `tags` - Editor tags of the node
+/// * `choices` - Choices offered by a choice node, in order
 ///
 /// # Example
 ///
@@ -121,6 +149,11 @@ pub struct DialogueStarted {
 ///             println!("Special node activated!");
 ///             // Trigger special game event
 ///         }
+///
+///         // Play a voice line for tagged nodes
+///         if event.tags.iter().any(|tag| tag == "voiced") {
+///             println!("{:?} says {:?}", event.speaker, event.text);
+///         }
 ///     }
 /// }
 /// ```
@@ -130,6 +163,112 @@ pub struct DialogueNodeActivated {
     pub entity: Entity,
     /// ID of the activated node
     pub node_id: NodeId,
+    /// Kind of the activated node
+    pub kind: DialogueNodeKind,
+    /// Text of a text node, or prompt of a choice node
+    pub text: Option<String>,
+    /// Speaker ID of the node
+    pub speaker: Option<String>,
+    /// Portrait identifier of the node.
+    ///
+    /// When the node has no portrait, the dialogue systems fill in the default portrait
+    /// of its speaker from the [`DialogueCharacters`] database.
+    pub portrait: Option<String>,
+    /// Editor tags of the node
+    pub tags: Vec<String>,
+    /// Choices offered by a choice node, in order
+    pub choices: Vec<DialogueChoiceInfo>,
+    /// Seconds a timed choice node waits before taking its default choice
+    pub choice_timeout: Option<f32>,
+}
+
+impl DialogueNodeActivated {
+    /// Creates the event for a node of a dialogue.
+    ///
+    /// # Returns
+    ///
+    /// The event, or `None` if the dialogue has no node with this ID
+    pub fn new(entity: Entity, dialogue: &DialogueAsset, node_id: NodeId) -> Option<Self> {
+        let graph = &dialogue.graph;
+        let node = graph.get_node(node_id)?;
+
+        let choices = match node.kind() {
+            DialogueNodeKind::Choice => graph
+                .get_connections(node_id)
+                .into_iter()
+                .enumerate()
+                .map(|(index, (target_id, connection))| DialogueChoiceInfo {
+                    index,
+                    target_id,
+                    label: connection.label.clone(),
+                })
+                .collect(),
+            DialogueNodeKind::Text
+            | DialogueNodeKind::Random
+            | DialogueNodeKind::Wait
+            | DialogueNodeKind::Input
+            | DialogueNodeKind::Return
+            | DialogueNodeKind::End
+            | DialogueNodeKind::Custom => Vec::new(),
+        };
+
+        Some(Self {
+            entity,
+            node_id,
+            kind: node.kind(),
+            text: node.display_text().map(str::to_string),
+            speaker: node.speaker().map(str::to_string),
+            portrait: node.portrait().map(str::to_string),
+            tags: graph
+                .node_metadata(node_id)
+                .map(|metadata| metadata.tags.clone())
+                .unwrap_or_default(),
+            choices,
+            choice_timeout: node.choice_timeout(),
+        })
+    }
+
+    /// Creates the event for the current node of a runner.
+    ///
+    /// Unlike [`new`](Self::new), the text is the line picked by the runner for a
+    /// text node with variants, and the choices leave out the once-only choices the
+    /// runner already took.
+    ///
+    /// # Returns
+    ///
+    /// The event, or `None` if the runner has no current node in the dialogue
+    pub fn from_runner(
+        entity: Entity,
+        dialogue: &DialogueAsset,
+        runner: &DialogueRunner,
+    ) -> Option<Self> {
+        let mut event = Self::new(entity, dialogue, runner.current_node_id?)?;
+        event.text = runner.current_text(dialogue).map(str::to_string);
+        if event.kind == DialogueNodeKind::Choice {
+            event.choices = runner
+                .available_choices(dialogue, event.node_id)
+                .into_iter()
+                .enumerate()
+                .map(|(index, (target_id, connection))| DialogueChoiceInfo {
+                    index,
+                    target_id,
+                    label: connection.label.clone(),
+                })
+                .collect();
+        }
+        Some(event)
+    }
+}
+
+/// A choice offered by an activated choice node.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DialogueChoiceInfo {
+    /// Index of the choice, as used by [`SelectDialogueChoice`]
+    pub index: usize,
+    /// ID of the node the choice leads to
+    pub target_id: NodeId,
+    /// Label of the choice
+    pub label: Option<String>,
 }
 
 /// Event sent when a player makes a choice in a dialogue.
@@ -169,6 +308,144 @@ pub struct DialogueChoiceMade {
     pub choice_index: usize,
 }
 
+/// Event sent when a timed choice node runs out of time.
+///
+/// The choice selected by the player is taken if there is one, otherwise the
+/// default choice of the node. The dialogue then moves on to it without a
+/// `DialogueChoiceMade` event. Use [`DialogueRunner::choice_time_left`] to show
+/// the countdown.
+///
+/// # Fields
+///
+/// * `entity` - Entity running the dialogue
+/// * `node_id` - ID of the choice node
+/// * `choice_index` - Index of the choice taken
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use funkus_dialogue_core::DialogueChoiceTimedOut;
+///
+/// fn on_hesitation(mut timed_out_events: EventReader<DialogueChoiceTimedOut>) {
+///     for event in timed_out_events.read() {
+///         println!("{:?} hesitated, taking choice {}", event.entity, event.choice_index);
+///     }
+/// }
+/// ```
+#[derive(Event, Debug, Clone)]
+pub struct DialogueChoiceTimedOut {
+    /// Entity running the dialogue
+    pub entity: Entity,
+    /// ID of the choice node
+    pub node_id: NodeId,
+    /// Index of the choice taken
+    pub choice_index: usize,
+}
+
+/// Event sent when a dialogue couldn't be started.
+///
+/// Start requests wait until their dialogue asset is loaded. This event is emitted
+/// instead of `DialogueStarted` if the asset fails to load or isn't being loaded, the
+/// entity is despawned while waiting, or the requested entry point or start node
+/// doesn't exist.
+///
+/// # Fields
+///
+/// * `entity` - Entity that should have run the dialogue
+/// * `error` - Why the dialogue couldn't start
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use funkus_dialogue_core::DialogueStartFailed;
+///
+/// fn on_dialogue_start_failed(mut failed_events: EventReader<DialogueStartFailed>) {
+///     for event in failed_events.read() {
+///         println!("Dialogue on {:?} failed to start: {}", event.entity, event.error);
+///     }
+/// }
+/// ```
+#[derive(Event, Debug, Clone)]
+pub struct DialogueStartFailed {
+    /// Entity that should have run the dialogue
+    pub entity: Entity,
+    /// Why the dialogue couldn't start
+    pub error: DialogueError,
+}
+
+/// Event sent when an action on a running dialogue fails.
+///
+/// Failures to start a dialogue, advance it or select a choice are all reported
+/// here. How the dialogue continues afterwards depends on the
+/// [`DialogueErrorPolicy`](crate::runtime::DialogueErrorPolicy) of its runner.
+///
+/// # Fields
+///
+/// * `entity` - Entity running the dialogue
+/// * `error` - The error that occurred
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use funkus_dialogue_core::{DialogueError, DialogueErrorOccurred};
+///
+/// fn report_dialogue_errors(mut error_events: EventReader<DialogueErrorOccurred>) {
+///     for event in error_events.read() {
+///         if let DialogueError::NextNodeNotFound(node_id) = event.error {
+///             println!("Dialogue on {:?} points to missing node {:?}", event.entity, node_id);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Event, Debug, Clone)]
+pub struct DialogueErrorOccurred {
+    /// Entity running the dialogue
+    pub entity: Entity,
+    /// The error that occurred
+    pub error: DialogueError,
+}
+
+/// Event sent when a running dialogue couldn't stay on its node after a hot reload.
+///
+/// When a dialogue asset is modified, running dialogues stay on their current node.
+/// If that node was removed from the dialogue, the runner falls back to the start
+/// node and this event is emitted so tools can warn the writer.
+///
+/// # Fields
+///
+/// * `entity` - Entity running the dialogue
+/// * `missing_node_id` - ID of the node that no longer exists
+/// * `fallback_node_id` - ID of the node the runner moved to, or None if the dialogue
+///   has no valid start node either
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use funkus_dialogue_core::DialogueReloadWarning;
+///
+/// fn report_reload_warnings(mut warning_events: EventReader<DialogueReloadWarning>) {
+///     for event in warning_events.read() {
+///         println!(
+///             "Node {:?} was removed, restarted at {:?}",
+///             event.missing_node_id, event.fallback_node_id
+///         );
+///     }
+/// }
+/// ```
+#[derive(Event, Debug, Clone)]
+pub struct DialogueReloadWarning {
+    /// Entity running the dialogue
+    pub entity: Entity,
+    /// ID of the node that no longer exists
+    pub missing_node_id: NodeId,
+    /// ID of the node the runner moved to
+    pub fallback_node_id: Option<NodeId>,
+}
+
 /// Event sent when a dialogue ends.
 ///
 /// This event is emitted when a dialogue completes, either by reaching
@@ -179,6 +456,7 @@ pub struct DialogueChoiceMade {
 ///
 /// * `entity` - Entity running the dialogue
 /// * `normal_exit` - Whether the dialogue ended normally (as opposed to being forcibly stopped)
+/// * `outcome` - Outcome of the end node the dialogue reached, if any
 ///
 /// # Example
 ///
@@ -188,10 +466,11 @@ pub struct DialogueChoiceMade {
 ///
 /// fn on_dialogue_end(mut end_events: EventReader<DialogueEnded>) {
 ///     for event in end_events.read() {
-///         if event.normal_exit {
-///             println!("Dialogue completed normally");
-///         } else {
-///             println!("Dialogue was interrupted");
+///         match event.outcome.as_deref() {
+///             Some("quest_accepted") => println!("The player took the quest"),
+///             Some("fight") => println!("Draw your sword!"),
+///             _ if event.normal_exit => println!("Dialogue completed normally"),
+///             _ => println!("Dialogue was interrupted"),
 ///         }
 ///         // Return camera to normal, resume gameplay, etc.
 ///     }
@@ -203,6 +482,8 @@ pub struct DialogueEnded {
     pub entity: Entity,
     /// Whether the dialogue ended normally (as opposed to being forcibly stopped)
     pub normal_exit: bool,
+    /// Outcome of the end node the dialogue reached, or None if it ended elsewhere
+    pub outcome: Option<String>,
 }
 
 /// Event to request advancing the dialogue.
@@ -244,7 +525,8 @@ pub struct AdvanceDialogue {
 /// Event to request selecting a choice.
 ///
 /// Send this event to select a choice in a choice node.
-/// The choice isn't confirmed until an AdvanceDialogue event is sent.
+/// The choice isn't confirmed until an AdvanceDialogue event is sent, which can be
+/// sent in the same frame.
 ///
 /// # Fields
 ///
@@ -293,6 +575,88 @@ pub struct SelectDialogueChoice {
     pub choice_index: usize,
 }
 
+/// Event to request submitting an input.
+///
+/// Send this event when the player confirms what they typed on an input node.
+/// Unlike choices, submitting an input moves the dialogue on immediately. An input
+/// that breaks one of the node's rules is reported as a `DialogueErrorOccurred`
+/// and the dialogue stays on the input node.
+///
+/// # Fields
+///
+/// * `entity` - Entity running the dialogue
+/// * `value` - The text typed by the player
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use funkus_dialogue::{SubmitDialogueInput, DialogueRunner, DialogueState};
+///
+/// fn submit_player_name(
+///     dialogue_query: Query<(Entity, &DialogueRunner)>,
+///     mut submit_events: EventWriter<SubmitDialogueInput>,
+/// ) {
+///     for (entity, runner) in dialogue_query.iter() {
+///         if runner.state == DialogueState::WaitingForInput {
+///             submit_events.send(SubmitDialogueInput {
+///                 entity,
+///                 value: "Ada".to_string(),
+///             });
+///         }
+///     }
+/// }
+/// ```
+#[derive(Event, Debug, Clone)]
+pub struct SubmitDialogueInput {
+    /// Entity running the dialogue
+    pub entity: Entity,
+    /// The text typed by the player
+    pub value: String,
+}
+
+/// Event to request moving a dialogue to any node.
+///
+/// Send this event to redirect a conversation mid-flow, for instance when an NPC
+/// gets interrupted. The dialogue enters the node as if it had advanced to it, and
+/// a `DialogueNodeActivated` event is sent. If the dialogue isn't running, it is
+/// started at the node. A node that doesn't exist is reported as a
+/// `DialogueErrorOccurred` and the dialogue is left as it was.
+///
+/// # Fields
+///
+/// * `entity` - Entity running the dialogue
+/// * `node_id` - ID of the node to move to
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use funkus_dialogue::{JumpDialogue, NodeId};
+///
+/// #[derive(Event)]
+/// struct NpcHit(Entity);
+///
+/// fn interrupt_on_hit(
+///     mut hit_events: EventReader<NpcHit>,
+///     mut jump_events: EventWriter<JumpDialogue>,
+/// ) {
+///     for NpcHit(npc) in hit_events.read() {
+///         jump_events.send(JumpDialogue {
+///             entity: *npc,
+///             node_id: NodeId(42),
+///         });
+///     }
+/// }
+/// ```
+#[derive(Event, Debug, Clone)]
+pub struct JumpDialogue {
+    /// Entity running the dialogue
+    pub entity: Entity,
+    /// ID of the node to move to
+    pub node_id: NodeId,
+}
+
 /// Event to request starting a dialogue.
 ///
 /// Send this event to start a dialogue on an entity.
@@ -302,6 +666,7 @@ pub struct SelectDialogueChoice {
 ///
 /// * `entity` - Entity to attach the dialogue runner to
 /// * `dialogue_handle` - Handle to the dialogue asset
+/// * `entry_point` - Optional name of the entry point to start from
 ///
 /// # Example
 ///
@@ -329,6 +694,7 @@ pub struct SelectDialogueChoice {
 ///                 start_events.send(StartDialogue {
 ///                     entity: player,
 ///                     dialogue_handle,
+///                     entry_point: None,
 ///                 });
 ///             }
 ///         }
@@ -341,6 +707,11 @@ pub struct StartDialogue {
     pub entity: Entity,
     /// Handle to the dialogue asset
     pub dialogue_handle: Handle<crate::asset::DialogueAsset>,
+    /// Name of the entry point to start from.
+    ///
+    /// When `None`, the first entry point whose condition holds is used,
+    /// falling back to the start node of the dialogue.
+    pub entry_point: Option<String>,
 }
 
 /// Event to request stopping a dialogue.
@@ -377,3 +748,280 @@ pub struct StopDialogue {
     /// Entity running the dialogue
     pub entity: Entity,
 }
+
+/// System parameter that sends dialogue notifications both ways.
+///
+/// Every notification is sent as a buffered event for `EventReader`s, and triggered
+/// on the dialogue entity for observers. Activated nodes without a portrait get the
+/// default portrait of their speaker, if a [`DialogueCharacters`] database is loaded.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use funkus_dialogue_core::DialogueNodeActivated;
+///
+/// fn setup(mut commands: Commands) {
+///     commands
+///         .spawn(Name::new("Guide"))
+///         .observe(|trigger: Trigger<DialogueNodeActivated>| {
+///             println!("Guide reached node {:?}", trigger.event().node_id);
+///         });
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct DialogueNotifications<'w, 's> {
+    commands: Commands<'w, 's>,
+    started_events: EventWriter<'w, DialogueStarted>,
+    ended_events: EventWriter<'w, DialogueEnded>,
+    node_activated_events: EventWriter<'w, DialogueNodeActivated>,
+    choice_made_events: EventWriter<'w, DialogueChoiceMade>,
+    choice_timed_out_events: EventWriter<'w, DialogueChoiceTimedOut>,
+    error_events: EventWriter<'w, DialogueErrorOccurred>,
+    characters: Option<Res<'w, DialogueCharacters>>,
+    character_databases: Option<Res<'w, Assets<CharacterDatabase>>>,
+}
+
+impl DialogueNotifications<'_, '_> {
+    /// Notifies that a dialogue has started.
+    pub fn started(&mut self, event: DialogueStarted) {
+        self.commands.trigger_targets(event.clone(), event.entity);
+        self.started_events.send(event);
+    }
+
+    /// Notifies that a dialogue has ended.
+    pub fn ended(&mut self, event: DialogueEnded) {
+        self.commands.trigger_targets(event.clone(), event.entity);
+        self.ended_events.send(event);
+    }
+
+    /// Notifies that a dialogue node has been activated.
+    pub fn node_activated(&mut self, mut event: DialogueNodeActivated) {
+        if event.portrait.is_none() {
+            event.portrait = self.default_portrait(event.speaker.as_deref());
+        }
+        self.commands.trigger_targets(event.clone(), event.entity);
+        self.node_activated_events.send(event);
+    }
+
+    /// Notifies that a choice has been made.
+    pub fn choice_made(&mut self, event: DialogueChoiceMade) {
+        self.commands.trigger_targets(event.clone(), event.entity);
+        self.choice_made_events.send(event);
+    }
+
+    /// Notifies that a timed choice ran out of time.
+    pub fn choice_timed_out(&mut self, event: DialogueChoiceTimedOut) {
+        self.commands.trigger_targets(event.clone(), event.entity);
+        self.choice_timed_out_events.send(event);
+    }
+
+    /// Notifies that an action on a dialogue failed.
+    pub fn error_occurred(&mut self, event: DialogueErrorOccurred) {
+        self.commands.trigger_targets(event.clone(), event.entity);
+        self.error_events.send(event);
+    }
+
+    /// Looks up the default portrait of a speaker in the character database.
+    fn default_portrait(&self, speaker: Option<&str>) -> Option<String> {
+        let database = self
+            .character_databases
+            .as_ref()?
+            .get(&self.characters.as_ref()?.0)?;
+        database.default_portrait(speaker?).map(str::to_string)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asset::CharacterDefinition;
+    use crate::graph::{ConnectionData, DialogueGraph, DialogueNode};
+    use crate::DialoguePlugin;
+
+    /// Creates a headless app running the dialogue systems.
+    fn dialogue_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default(), DialoguePlugin));
+        app
+    }
+
+    /// Takes the events of a type sent so far.
+    fn drain_events<E: Event>(app: &mut App) -> Vec<E> {
+        app.world_mut()
+            .resource_mut::<Events<E>>()
+            .drain()
+            .collect()
+    }
+
+    #[test]
+    fn test_node_activated_payload() {
+        let mut graph = DialogueGraph::new(NodeId(1))
+            .with_node(
+                DialogueNode::choice(NodeId(1))
+                    .with_prompt("Where to?")
+                    .unwrap()
+                    .with_speaker("guide"),
+            )
+            .with_node(DialogueNode::text(NodeId(2), "North"))
+            .with_node(DialogueNode::text(NodeId(3), "South"));
+        graph
+            .connect(
+                NodeId(1),
+                NodeId(2),
+                ConnectionData::new(Some("North".into())),
+            )
+            .unwrap();
+        graph
+            .connect(
+                NodeId(1),
+                NodeId(3),
+                ConnectionData::new(Some("South".into())),
+            )
+            .unwrap();
+        graph
+            .node_metadata_mut(NodeId(1))
+            .unwrap()
+            .add_tag("voiced");
+        let dialogue = DialogueAsset::new(graph);
+
+        let event = DialogueNodeActivated::new(Entity::PLACEHOLDER, &dialogue, NodeId(1)).unwrap();
+        assert_eq!(event.kind, DialogueNodeKind::Choice);
+        assert_eq!(event.text.as_deref(), Some("Where to?"));
+        assert_eq!(event.speaker.as_deref(), Some("guide"));
+        assert_eq!(event.tags, vec!["voiced".to_string()]);
+        let labels: Vec<_> = event.choices.iter().map(|c| c.label.as_deref()).collect();
+        assert_eq!(labels, vec![Some("North"), Some("South")]);
+        assert_eq!(event.choices[1].target_id, NodeId(3));
+
+        let event = DialogueNodeActivated::new(Entity::PLACEHOLDER, &dialogue, NodeId(2)).unwrap();
+        assert_eq!(event.kind, DialogueNodeKind::Text);
+        assert!(event.choices.is_empty());
+
+        assert!(DialogueNodeActivated::new(Entity::PLACEHOLDER, &dialogue, NodeId(9)).is_none());
+    }
+
+    #[test]
+    fn test_node_activated_default_portrait() {
+        let mut graph = DialogueGraph::new(NodeId(1))
+            .with_node(DialogueNode::text(NodeId(1), "Hello").with_speaker("guide"))
+            .with_node(
+                DialogueNode::text(NodeId(2), "Smile!")
+                    .with_speaker("guide")
+                    .with_portrait("portraits/guide_happy.png"),
+            );
+        graph
+            .connect(NodeId(1), NodeId(2), ConnectionData::new(None))
+            .unwrap();
+
+        let mut app = dialogue_app();
+        let database = CharacterDatabase::default().with_character(
+            "guide",
+            CharacterDefinition {
+                display_name: "Guide".to_string(),
+                default_portrait: Some("portraits/guide.png".to_string()),
+                ..Default::default()
+            },
+        );
+        let database = app
+            .world_mut()
+            .resource_mut::<Assets<CharacterDatabase>>()
+            .add(database);
+        app.insert_resource(DialogueCharacters(database));
+        let dialogue_handle = app
+            .world_mut()
+            .resource_mut::<Assets<DialogueAsset>>()
+            .add(DialogueAsset::new(graph));
+        let entity = app.world_mut().spawn_empty().id();
+
+        app.world_mut().send_event(StartDialogue {
+            entity,
+            dialogue_handle,
+            entry_point: None,
+        });
+        app.update();
+        app.world_mut().send_event(AdvanceDialogue { entity });
+        app.update();
+
+        let portraits: Vec<_> = drain_events::<DialogueNodeActivated>(&mut app)
+            .into_iter()
+            .map(|event| event.portrait)
+            .collect();
+        assert_eq!(
+            portraits,
+            vec![
+                Some("portraits/guide.png".to_string()),
+                Some("portraits/guide_happy.png".to_string()),
+            ]
+        );
+    }
+
+    /// Notifications observed on dialogue entities, as (entity, notification) pairs.
+    #[derive(Resource, Default)]
+    struct Observed(Vec<(Entity, String)>);
+
+    /// Spawns an entity recording the notifications triggered on it.
+    fn spawn_observed(app: &mut App) -> Entity {
+        let mut entity = app.world_mut().spawn_empty();
+        entity
+            .observe(
+                |trigger: Trigger<DialogueStarted>, mut observed: ResMut<Observed>| {
+                    let name = format!("started {:?}", trigger.event().start_node_id);
+                    observed.0.push((trigger.entity(), name));
+                },
+            )
+            .observe(
+                |trigger: Trigger<DialogueNodeActivated>, mut observed: ResMut<Observed>| {
+                    let name = format!("activated {:?}", trigger.event().node_id);
+                    observed.0.push((trigger.entity(), name));
+                },
+            )
+            .observe(
+                |trigger: Trigger<DialogueEnded>, mut observed: ResMut<Observed>| {
+                    let name = format!("ended {}", trigger.event().normal_exit);
+                    observed.0.push((trigger.entity(), name));
+                },
+            );
+        entity.id()
+    }
+
+    #[test]
+    fn test_notifications_trigger_on_entity() {
+        let mut graph = DialogueGraph::new(NodeId(1))
+            .with_node(DialogueNode::text(NodeId(1), "Hello"))
+            .with_node(DialogueNode::text(NodeId(2), "Bye"));
+        graph
+            .connect(NodeId(1), NodeId(2), ConnectionData::new(None))
+            .unwrap();
+
+        let mut app = dialogue_app();
+        app.init_resource::<Observed>();
+        let dialogue_handle = app
+            .world_mut()
+            .resource_mut::<Assets<DialogueAsset>>()
+            .add(DialogueAsset::new(graph));
+        let entity = spawn_observed(&mut app);
+        // Observers of another entity aren't triggered
+        spawn_observed(&mut app);
+
+        app.world_mut().send_event(StartDialogue {
+            entity,
+            dialogue_handle,
+            entry_point: None,
+        });
+        app.update();
+        for _ in 0..2 {
+            app.world_mut().send_event(AdvanceDialogue { entity });
+            app.update();
+        }
+
+        let expected = [
+            "started NodeId(1)",
+            "activated NodeId(1)",
+            "activated NodeId(2)",
+            "ended true",
+        ]
+        .map(|name| (entity, name.to_string()));
+        assert_eq!(app.world().resource::<Observed>().0, expected);
+    }
+}
@@ -4,14 +4,15 @@
 //! with its nodes, connections, and metadata.
 
 use bevy::prelude::*;
-use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::graph::{DiGraph, EdgeIndex, NodeIndex};
 use petgraph::visit::{EdgeRef, IntoNodeReferences};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use super::node::NodeId;
-use super::nodes::DialogueNode;
-use super::{ConnectionData, DialogueElement};
+use super::nodes::{AdvanceTiming, DialogueNode, InputRule, RandomMode, TextVariantMode};
+use super::{ConnectionData, DialogueElement, EntryPoint, NodeMetadata};
+use crate::error::GraphError;
 
 /// Represents a complete dialogue graph with nodes and metadata.
 ///
@@ -28,7 +29,9 @@ use super::{ConnectionData, DialogueElement};
 ///
 /// - `graph`: The underlying petgraph directed graph
 /// - `node_indices`: Mapping from NodeId to petgraph NodeIndex
+/// - `metadata`: Editor metadata of the nodes, such as their positions
 /// - `start_node`: The starting node ID for this dialogue
+/// - `entry_points`: Named nodes the dialogue can also start from
 /// - `name`: Optional name or identifier for this dialogue
 ///
 /// # Example
@@ -67,8 +70,13 @@ pub struct DialogueGraph {
     /// 3. It provides O(1) lookups when translating between our IDs and petgraph's indices
     #[reflect(ignore)]
     node_indices: HashMap<NodeId, NodeIndex>,
+    /// Editor metadata of the nodes, only present for nodes that have some
+    #[reflect(ignore)]
+    metadata: HashMap<NodeId, NodeMetadata>,
     /// The starting node ID for this dialogue
     pub start_node: NodeId,
+    /// Named nodes the dialogue can also start from, in the order they are checked
+    pub entry_points: Vec<EntryPoint>,
     /// Optional name or identifier for this dialogue
     pub name: Option<String>,
 }
@@ -84,21 +92,53 @@ impl Serialize for DialogueGraph {
     {
         // Define our new serialization format
         #[derive(Serialize)]
-        struct SerialNode {
+        struct SerialNode<'a> {
             #[serde(rename = "type")]
-            node_type: &'static str,
+            node_type: &'a str,
             id: NodeId,
             // Text node fields
             #[serde(skip_serializing_if = "Option::is_none")]
             text: Option<String>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            variants: Option<&'a [String]>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            variant_mode: Option<TextVariantMode>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            advance: Option<AdvanceTiming>,
             // Choice node fields
             #[serde(skip_serializing_if = "Option::is_none")]
             prompt: Option<String>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            timeout: Option<f32>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            default_choice: Option<usize>,
+            #[serde(skip_serializing_if = "is_false")]
+            hub: bool,
             // Common fields
             #[serde(skip_serializing_if = "Option::is_none")]
             speaker: Option<String>,
             #[serde(skip_serializing_if = "Option::is_none")]
             portrait: Option<String>,
+            // Random node fields
+            #[serde(skip_serializing_if = "Option::is_none")]
+            mode: Option<RandomMode>,
+            // Wait node fields
+            #[serde(skip_serializing_if = "Option::is_none")]
+            duration: Option<f32>,
+            // Input node fields
+            #[serde(skip_serializing_if = "Option::is_none")]
+            variable: Option<&'a str>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            rules: Option<&'a [InputRule]>,
+            // End node fields
+            #[serde(skip_serializing_if = "Option::is_none")]
+            outcome: Option<&'a str>,
+            // Custom node fields
+            #[serde(skip_serializing_if = "Option::is_none")]
+            data: Option<&'a serde_json::Value>,
+            // Editor metadata
+            #[serde(skip_serializing_if = "Option::is_none")]
+            editor: Option<NodeMetadata>,
         }
 
         #[derive(Serialize)]
@@ -106,13 +146,33 @@ impl Serialize for DialogueGraph {
             from: NodeId,
             to: NodeId,
             label: Option<String>,
+            #[serde(skip_serializing_if = "is_default_weight")]
+            weight: f32,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            condition: Option<String>,
+            #[serde(skip_serializing_if = "is_false")]
+            once: bool,
+        }
+
+        fn is_default_weight(weight: &f32) -> bool {
+            *weight == ConnectionData::default_weight()
+        }
+
+        fn is_false(value: &bool) -> bool {
+            !*value
+        }
+
+        fn is_empty(entry_points: &&[EntryPoint]) -> bool {
+            entry_points.is_empty()
         }
 
         #[derive(Serialize)]
-        struct SerialGraph {
-            nodes: Vec<SerialNode>,
+        struct SerialGraph<'a> {
+            nodes: Vec<SerialNode<'a>>,
             connections: Vec<SerialConnection>,
             start_node: NodeId,
+            #[serde(skip_serializing_if = "is_empty")]
+            entry_points: &'a [EntryPoint],
             name: Option<String>,
         }
 
@@ -120,33 +180,100 @@ impl Serialize for DialogueGraph {
         let mut nodes = Vec::new();
         let mut connections = Vec::new();
 
-        // Process each node
-        for node_id in self.node_ids() {
+        // Process each node, sorted by ID so saved files are stable
+        let mut node_ids = self.node_ids();
+        node_ids.sort_by_key(|id| id.0);
+        for node_id in node_ids {
             if let Some(node) = self.get_node(node_id) {
                 // Extract node data based on type
-                let (node_type, text, prompt) = match node {
-                    DialogueNode::Text { text, .. } => ("Text", Some(text.clone()), None),
-                    DialogueNode::Choice { prompt, .. } => ("Choice", None, prompt.clone()),
+                let (node_type, text, prompt, mode, data) = match node {
+                    DialogueNode::Text { text, .. } => {
+                        ("Text", Some(text.clone()), None, None, None)
+                    }
+                    DialogueNode::Choice { prompt, .. } => {
+                        ("Choice", None, prompt.clone(), None, None)
+                    }
+                    DialogueNode::Random { mode, .. } => ("Random", None, None, Some(*mode), None),
+                    DialogueNode::Wait { .. } => ("Wait", None, None, None, None),
+                    DialogueNode::Return { .. } => ("Return", None, None, None, None),
+                    DialogueNode::End { .. } => ("End", None, None, None, None),
+                    DialogueNode::Input { prompt, .. } => {
+                        ("Input", None, prompt.clone(), None, None)
+                    }
+                    DialogueNode::Custom {
+                        node_type, data, ..
+                    } => (node_type.as_str(), None, None, None, Some(data)),
                 };
 
-                // Get speaker and portrait from either node type
-                let (speaker, portrait) = match node {
+                // Only write the variant and timing fields of text nodes that use them
+                let (variants, variant_mode, advance) = match node {
                     DialogueNode::Text {
-                        speaker, portrait, ..
-                    }
-                    | DialogueNode::Choice {
-                        speaker, portrait, ..
-                    } => (speaker.clone(), portrait.clone()),
+                        variants,
+                        variant_mode,
+                        advance,
+                        ..
+                    } => (
+                        (!variants.is_empty()).then_some(variants.as_slice()),
+                        (*variant_mode != TextVariantMode::default()).then_some(*variant_mode),
+                        (*advance != AdvanceTiming::default()).then_some(*advance),
+                    ),
+                    _ => (None, None, None),
+                };
+                let duration = match node {
+                    DialogueNode::Wait { duration, .. } => Some(*duration),
+                    _ => None,
+                };
+                let (timeout, default_choice) = match node {
+                    DialogueNode::Choice {
+                        timeout: Some(timeout),
+                        default_choice,
+                        ..
+                    } => (Some(*timeout), Some(*default_choice)),
+                    _ => (None, None),
+                };
+                let hub = matches!(node, DialogueNode::Choice { hub: true, .. });
+                let outcome = match node {
+                    DialogueNode::End { outcome, .. } => outcome.as_deref(),
+                    _ => None,
+                };
+                let (variable, rules) = match node {
+                    DialogueNode::Input {
+                        variable, rules, ..
+                    } => (
+                        Some(variable.as_str()),
+                        (!rules.is_empty()).then_some(rules.as_slice()),
+                    ),
+                    _ => (None, None),
                 };
 
+                // Get speaker and portrait from any node type that has them
+                let speaker = node.speaker().map(str::to_string);
+                let portrait = node.portrait().map(str::to_string);
+
                 // Add node to the collection
                 nodes.push(SerialNode {
                     node_type,
                     id: node_id,
                     text,
+                    variants,
+                    variant_mode,
+                    advance,
                     prompt,
+                    timeout,
+                    default_choice,
+                    hub,
                     speaker,
                     portrait,
+                    mode,
+                    duration,
+                    variable,
+                    rules,
+                    outcome,
+                    data,
+                    editor: self
+                        .node_metadata(node_id)
+                        .filter(|metadata| !metadata.is_empty())
+                        .cloned(),
                 });
 
                 // Process all connections from this node
@@ -155,6 +282,9 @@ impl Serialize for DialogueGraph {
                         from: node_id,
                         to: target_id,
                         label: conn_data.label.clone(),
+                        weight: conn_data.weight,
+                        condition: conn_data.condition.clone(),
+                        once: conn_data.once,
                     });
                 }
             }
@@ -165,6 +295,7 @@ impl Serialize for DialogueGraph {
             nodes,
             connections,
             start_node: self.start_node,
+            entry_points: &self.entry_points,
             name: self.name.clone(),
         };
 
@@ -184,9 +315,35 @@ impl<'de> Deserialize<'de> for DialogueGraph {
             node_type: String,
             id: NodeId,
             text: Option<String>,
+            #[serde(default)]
+            variants: Vec<String>,
+            #[serde(default)]
+            variant_mode: TextVariantMode,
+            #[serde(default)]
+            advance: AdvanceTiming,
             prompt: Option<String>,
+            #[serde(default)]
+            timeout: Option<f32>,
+            #[serde(default)]
+            default_choice: usize,
+            #[serde(default)]
+            hub: bool,
             speaker: Option<String>,
             portrait: Option<String>,
+            #[serde(default)]
+            mode: Option<RandomMode>,
+            #[serde(default)]
+            duration: Option<f32>,
+            #[serde(default)]
+            variable: Option<String>,
+            #[serde(default)]
+            rules: Vec<InputRule>,
+            #[serde(default)]
+            outcome: Option<String>,
+            #[serde(default)]
+            data: Option<serde_json::Value>,
+            #[serde(default)]
+            editor: Option<NodeMetadata>,
         }
 
         #[derive(Deserialize)]
@@ -194,6 +351,12 @@ impl<'de> Deserialize<'de> for DialogueGraph {
             from: NodeId,
             to: NodeId,
             label: Option<String>,
+            #[serde(default = "ConnectionData::default_weight")]
+            weight: f32,
+            #[serde(default)]
+            condition: Option<String>,
+            #[serde(default)]
+            once: bool,
         }
 
         #[derive(Deserialize)]
@@ -201,6 +364,8 @@ impl<'de> Deserialize<'de> for DialogueGraph {
             nodes: Vec<SerialNode>,
             connections: Vec<SerialConnection>,
             start_node: NodeId,
+            #[serde(default)]
+            entry_points: Vec<EntryPoint>,
             name: Option<String>,
         }
 
@@ -209,6 +374,7 @@ impl<'de> Deserialize<'de> for DialogueGraph {
 
         // Create a new graph
         let mut graph = DialogueGraph::new(data.start_node);
+        graph.entry_points = data.entry_points;
         graph.name = data.name;
 
         // Add all nodes first
@@ -221,9 +387,17 @@ impl<'de> Deserialize<'de> for DialogueGraph {
                         node_data.text.clone().unwrap_or_default(),
                     );
                     if let DialogueNode::Text {
-                        speaker, portrait, ..
+                        variants,
+                        variant_mode,
+                        advance,
+                        speaker,
+                        portrait,
+                        ..
                     } = &mut node
                     {
+                        *variants = node_data.variants.clone();
+                        *variant_mode = node_data.variant_mode;
+                        *advance = node_data.advance;
                         *speaker = node_data.speaker.clone();
                         *portrait = node_data.portrait.clone();
                     }
@@ -233,26 +407,73 @@ impl<'de> Deserialize<'de> for DialogueGraph {
                     let mut node = DialogueNode::choice(node_data.id);
                     if let DialogueNode::Choice {
                         prompt,
+                        timeout,
+                        default_choice,
+                        hub,
+                        speaker,
+                        portrait,
+                        ..
+                    } = &mut node
+                    {
+                        *prompt = node_data.prompt.clone();
+                        *timeout = node_data.timeout;
+                        *default_choice = node_data.default_choice;
+                        *hub = node_data.hub;
+                        *speaker = node_data.speaker.clone();
+                        *portrait = node_data.portrait.clone();
+                    }
+                    node
+                }
+                "Random" => DialogueNode::random(node_data.id, node_data.mode.unwrap_or_default()),
+                "Wait" => DialogueNode::wait(node_data.id, node_data.duration.unwrap_or_default()),
+                "Return" => DialogueNode::return_to_hub(node_data.id),
+                "End" => DialogueNode::End {
+                    id: node_data.id,
+                    outcome: node_data.outcome.clone(),
+                },
+                "Input" => {
+                    let mut node = DialogueNode::input(
+                        node_data.id,
+                        node_data.variable.clone().unwrap_or_default(),
+                    );
+                    if let DialogueNode::Input {
+                        prompt,
+                        rules,
                         speaker,
                         portrait,
                         ..
                     } = &mut node
                     {
                         *prompt = node_data.prompt.clone();
+                        *rules = node_data.rules.clone();
                         *speaker = node_data.speaker.clone();
                         *portrait = node_data.portrait.clone();
                     }
                     node
                 }
-                _ => continue, // Skip unknown node types
+                // Other types are custom nodes, handled by the type registered at runtime
+                node_type => DialogueNode::custom(
+                    node_data.id,
+                    node_type,
+                    node_data.data.clone().unwrap_or_default(),
+                ),
             };
 
             graph.add_node(node);
+            if let Some(metadata) = &node_data.editor {
+                graph.metadata.insert(node_data.id, metadata.clone());
+            }
         }
 
         // Add all connections
         for conn in &data.connections {
-            let _ = graph.connect(conn.from, conn.to, ConnectionData::new(conn.label.clone()));
+            let connection = ConnectionData {
+                label: conn.label.clone(),
+                weight: conn.weight,
+                condition: conn.condition.clone(),
+                once: conn.once,
+            };
+            let _ = graph.connect(conn.from, conn.to, connection);
         }
 
         Ok(graph)
@@ -282,7 +503,9 @@ impl DialogueGraph {
         Self {
             graph: DiGraph::new(),
             node_indices: HashMap::new(),
+            metadata: HashMap::new(),
             start_node,
+            entry_points: Vec::new(),
             name: None,
         }
     }
@@ -312,6 +535,34 @@ impl DialogueGraph {
         self
     }
 
+    /// Builder method to add an entry point, see [`add_entry_point`](Self::add_entry_point).
+    pub fn with_entry_point(mut self, entry_point: EntryPoint) -> Self {
+        self.add_entry_point(entry_point);
+        self
+    }
+
+    /// Adds an entry point to the graph.
+    ///
+    /// An existing entry point with the same name is replaced in place, otherwise the
+    /// entry point is checked after the existing ones.
+    pub fn add_entry_point(&mut self, entry_point: EntryPoint) {
+        match self
+            .entry_points
+            .iter_mut()
+            .find(|existing| existing.name == entry_point.name)
+        {
+            Some(existing) => *existing = entry_point,
+            None => self.entry_points.push(entry_point),
+        }
+    }
+
+    /// Gets an entry point by name.
+    pub fn entry_point(&self, name: &str) -> Option<&EntryPoint> {
+        self.entry_points
+            .iter()
+            .find(|entry_point| entry_point.name == name)
+    }
+
     /// Adds a node to the graph.
     ///
     /// This method adds a node to the petgraph structure and updates the node_indices map
@@ -438,57 +689,83 @@ impl DialogueGraph {
     ///
     /// This performs several checks to ensure the graph is valid:
     /// - All edge connections reference valid nodes
-    /// - The start node exists
-    /// - All nodes are reachable from the start node
+    /// - The start node and the nodes of the entry points exist
+    /// - All nodes are reachable from the start node or an entry point
+    /// - The default choice of every timed choice node is one of its choices
     ///
     /// # Returns
     ///
-    /// Ok(()) if the graph is valid, or an error message describing the issue
-    pub fn validate(&self) -> Result<(), String> {
+    /// Ok(()) if the graph is valid, or an error describing the issue
+    pub fn validate(&self) -> Result<(), GraphError> {
         // Check that all edges point to valid target nodes
         for edge in self.graph.edge_indices() {
             if let Some((source_idx, target_idx)) = self.graph.edge_endpoints(edge) {
-                // Find the NodeId for the source
-                let source_id = self
-                    .node_indices
-                    .iter()
-                    .find_map(|(id, &idx)| if idx == source_idx { Some(id) } else { None })
-                    .ok_or_else(|| {
-                        format!(
-                            "Internal error: Edge source index {:?} has no NodeId mapping",
-                            source_idx
-                        )
-                    })?;
+                // Check that the source has a NodeId mapping
+                if !self.node_indices.values().any(|&idx| idx == source_idx) {
+                    return Err(GraphError::InconsistentMapping);
+                }
 
                 // Check if the target node exists by attempting to get its weight
                 if self.graph.node_weight(target_idx).is_none() {
-                    return Err(format!(
-                        "Node {:?} has an edge to non-existent target index {:?}",
-                        source_id, target_idx
-                    ));
+                    return Err(GraphError::InconsistentMapping);
                 }
             }
         }
 
         // Check that the start node exists
         if !self.node_indices.contains_key(&self.start_node) {
-            return Err(format!("Start node {:?} does not exist", self.start_node));
+            return Err(GraphError::MissingStartNode(self.start_node));
+        }
+
+        // Check that the entry point nodes exist
+        for entry_point in &self.entry_points {
+            if !self.node_indices.contains_key(&entry_point.node_id) {
+                return Err(GraphError::MissingEntryNode {
+                    name: entry_point.name.clone(),
+                    node_id: entry_point.node_id,
+                });
+            }
         }
 
         // Check for unreachable nodes using petgraph's algorithms
-        if let Some(&start_index) = self.node_indices.get(&self.start_node) {
-            // Using Petgraph's reachability analysis
-            for (node_id, &node_idx) in &self.node_indices {
-                if *node_id != self.start_node {
-                    let reachable = petgraph::algo::has_path_connecting(
-                        &self.graph,
-                        start_index,
-                        node_idx,
-                        None,
-                    );
-                    if !reachable {
-                        return Err(format!("Node {:?} is unreachable from start node", node_id));
-                    }
+        let roots: Vec<_> = std::iter::once(self.start_node)
+            .chain(
+                self.entry_points
+                    .iter()
+                    .map(|entry_point| entry_point.node_id),
+            )
+            .filter_map(|id| self.node_indices.get(&id).copied())
+            .collect();
+        // Using Petgraph's reachability analysis
+        for (node_id, &node_idx) in &self.node_indices {
+            let reachable = roots.iter().any(|&root_index| {
+                petgraph::algo::has_path_connecting(&self.graph, root_index, node_idx, None)
+            });
+            if !reachable {
+                return Err(GraphError::UnreachableNode(*node_id));
+            }
+        }
+
+        self.validate_default_choices()
+    }
+
+    /// Checks that the default choice of every timed choice node is one of its choices.
+    fn validate_default_choices(&self) -> Result<(), GraphError> {
+        for node in self.nodes_iter() {
+            if let DialogueNode::Choice {
+                id,
+                timeout: Some(_),
+                default_choice,
+                ..
+            } = node
+            {
+                let choices = self.get_connections(*id).len();
+                if *default_choice >= choices {
+                    return Err(GraphError::InvalidDefaultChoice {
+                        node_id: *id,
+                        default_choice: *default_choice,
+                        choices,
+                    });
                 }
             }
         }
@@ -548,16 +825,16 @@ impl DialogueGraph {
     /// # Returns
     ///
     /// Ok(()) if the update was successful, or an error if the node doesn't exist
-    pub fn update_node(&mut self, id: NodeId, node: DialogueNode) -> Result<(), String> {
+    pub fn update_node(&mut self, id: NodeId, node: DialogueNode) -> Result<(), GraphError> {
         if let Some(&idx) = self.node_indices.get(&id) {
             if let Some(existing_node) = self.graph.node_weight_mut(idx) {
                 *existing_node = node;
                 Ok(())
             } else {
-                Err(format!("Node {:?} found in indices but not in graph", id))
+                Err(GraphError::InconsistentMapping)
             }
         } else {
-            Err(format!("Node {:?} not found", id))
+            Err(GraphError::NodeNotFound(id))
         }
     }
 
@@ -574,7 +851,7 @@ impl DialogueGraph {
     /// # Returns
     ///
     /// Ok(()) if the removal was successful, or an error if the node doesn't exist
-    pub fn remove_node(&mut self, id: NodeId) -> Result<(), String> {
+    pub fn remove_node(&mut self, id: NodeId) -> Result<(), GraphError> {
         if let Some(&idx) = self.node_indices.get(&id) {
             // Before removing the node, check if it's the last node
             let is_last_node = idx.index() == self.graph.node_count() - 1;
@@ -587,7 +864,7 @@ impl DialogueGraph {
                     .node_indices
                     .iter()
                     .find_map(|(&nid, &nidx)| if nidx == last_idx { Some(nid) } else { None })
-                    .ok_or_else(|| "Failed to find last node ID".to_string())?;
+                    .ok_or(GraphError::InconsistentMapping)?;
                 Some(last_id)
             } else {
                 None
@@ -596,8 +873,9 @@ impl DialogueGraph {
             // Remove the node from petgraph
             self.graph.remove_node(idx);
 
-            // Remove the mapping for the deleted node
+            // Remove the mapping and metadata for the deleted node
             self.node_indices.remove(&id);
+            self.metadata.remove(&id);
 
             // Update the mapping for the last node that was moved
             if let Some(last_id) = last_node_id {
@@ -607,7 +885,7 @@ impl DialogueGraph {
 
             Ok(())
         } else {
-            Err(format!("Node {:?} not found", id))
+            Err(GraphError::NodeNotFound(id))
         }
     }
 
@@ -712,15 +990,15 @@ impl DialogueGraph {
         from: NodeId,
         to: NodeId,
         data: ConnectionData,
-    ) -> Result<(), String> {
+    ) -> Result<(), GraphError> {
         let from_idx = self
             .node_indices
             .get(&from)
-            .ok_or_else(|| format!("Source node {:?} not found", from))?;
+            .ok_or(GraphError::NodeNotFound(from))?;
         let to_idx = self
             .node_indices
             .get(&to)
-            .ok_or_else(|| format!("Target node {:?} not found", to))?;
+            .ok_or(GraphError::NodeNotFound(to))?;
 
         self.graph.add_edge(*from_idx, *to_idx, data);
         Ok(())
@@ -736,15 +1014,15 @@ impl DialogueGraph {
     /// # Returns
     ///
     /// Ok(()) if the connection was removed, or an error if no connection exists
-    pub fn disconnect(&mut self, from: NodeId, to: NodeId) -> Result<(), String> {
+    pub fn disconnect(&mut self, from: NodeId, to: NodeId) -> Result<(), GraphError> {
         let from_idx = self
             .node_indices
             .get(&from)
-            .ok_or_else(|| format!("Source node {:?} not found", from))?;
+            .ok_or(GraphError::NodeNotFound(from))?;
         let to_idx = self
             .node_indices
             .get(&to)
-            .ok_or_else(|| format!("Target node {:?} not found", to))?;
+            .ok_or(GraphError::NodeNotFound(to))?;
 
         // Find edge between these nodes (if any)
         let edges: Vec<_> = self
@@ -755,7 +1033,7 @@ impl DialogueGraph {
             .collect();
 
         if edges.is_empty() {
-            return Err(format!("No connection from {:?} to {:?}", from, to));
+            return Err(GraphError::ConnectionNotFound { from, to });
         }
 
         // Remove all edges between these nodes
@@ -768,6 +1046,9 @@ impl DialogueGraph {
 
     /// Get all connections from a node.
     ///
+    /// Connections are returned in the order they were added, which is also the
+    /// order of the choices of a choice node.
+    ///
     /// # Parameters
     ///
     /// * `from` - The ID of the node to get connections from
@@ -777,10 +1058,11 @@ impl DialogueGraph {
     /// A vector of (target NodeId, ConnectionData) pairs
     pub fn get_connections(&self, from: NodeId) -> Vec<(NodeId, &ConnectionData)> {
         if let Some(&node_idx) = self.node_indices.get(&from) {
+            // Petgraph walks outgoing edges from the newest to the oldest
             let edges = self
                 .graph
                 .edges_directed(node_idx, petgraph::Direction::Outgoing);
-            edges
+            let mut connections: Vec<_> = edges
                 .filter_map(|edge| {
                     let target_idx = edge.target();
                     // Find NodeId for this target using node_indices in reverse
@@ -794,11 +1076,137 @@ impl DialogueGraph {
 
                     Some((target_id, edge.weight()))
                 })
-                .collect()
+                .collect();
+            connections.reverse();
+            connections
         } else {
             Vec::new()
         }
     }
+
+    /// Gets a mutable reference to the data of a connection.
+    ///
+    /// If several connections link the same nodes, the first one is returned.
+    ///
+    /// # Parameters
+    ///
+    /// * `from` - The ID of the source node
+    /// * `to` - The ID of the target node
+    ///
+    /// # Returns
+    ///
+    /// The connection data, or None if the nodes aren't connected
+    pub fn get_connection_mut(&mut self, from: NodeId, to: NodeId) -> Option<&mut ConnectionData> {
+        let from_idx = *self.node_indices.get(&from)?;
+        let to_idx = *self.node_indices.get(&to)?;
+
+        let edge = self
+            .graph
+            .edges_directed(from_idx, petgraph::Direction::Outgoing)
+            .filter(|edge| edge.target() == to_idx)
+            .map(|edge| edge.id())
+            .last()?;
+        self.graph.edge_weight_mut(edge)
+    }
+
+    /// Gets a mutable reference to the data of a connection by its position.
+    ///
+    /// Unlike [`get_connection_mut`](Self::get_connection_mut), this tells apart several
+    /// connections linking the same nodes, such as two choices leading to the same node.
+    ///
+    /// # Parameters
+    ///
+    /// * `from` - The ID of the source node
+    /// * `index` - The position of the connection in [`get_connections`](Self::get_connections)
+    ///
+    /// # Returns
+    ///
+    /// The connection data, or None if the node has no connection at that position
+    pub fn get_connection_at_mut(
+        &mut self,
+        from: NodeId,
+        index: usize,
+    ) -> Option<&mut ConnectionData> {
+        let edge = self.connection_edge(from, index)?;
+        self.graph.edge_weight_mut(edge)
+    }
+
+    /// Removes a single connection by its position.
+    ///
+    /// Unlike [`disconnect`](Self::disconnect), other connections linking the same nodes
+    /// are kept.
+    ///
+    /// # Parameters
+    ///
+    /// * `from` - The ID of the source node
+    /// * `index` - The position of the connection in [`get_connections`](Self::get_connections)
+    ///
+    /// # Returns
+    ///
+    /// The data of the removed connection, or None if the node has no connection at that position
+    pub fn remove_connection_at(&mut self, from: NodeId, index: usize) -> Option<ConnectionData> {
+        let edge = self.connection_edge(from, index)?;
+        self.graph.remove_edge(edge)
+    }
+
+    /// Finds the edge of a connection by its position in [`get_connections`](Self::get_connections).
+    fn connection_edge(&self, from: NodeId, index: usize) -> Option<EdgeIndex> {
+        let from_idx = *self.node_indices.get(&from)?;
+
+        // Petgraph walks outgoing edges from the newest to the oldest
+        self.graph
+            .edges_directed(from_idx, petgraph::Direction::Outgoing)
+            .map(|edge| edge.id())
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .nth(index)
+    }
+
+    /// Gets the editor metadata of a node.
+    ///
+    /// # Returns
+    ///
+    /// The metadata of the node, or None if the node has no metadata
+    pub fn node_metadata(&self, id: NodeId) -> Option<&NodeMetadata> {
+        self.metadata.get(&id)
+    }
+
+    /// Gets all nodes with the given tag, sorted by ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use funkus_dialogue_core::graph::{DialogueGraph, DialogueNode, NodeId};
+    ///
+    /// let mut graph = DialogueGraph::new(NodeId(1))
+    ///     .with_node(DialogueNode::text(NodeId(1), "Press A to jump"));
+    /// graph.node_metadata_mut(NodeId(1)).unwrap().add_tag("tutorial");
+    ///
+    /// assert_eq!(graph.nodes_with_tag("tutorial"), vec![NodeId(1)]);
+    /// ```
+    pub fn nodes_with_tag(&self, tag: &str) -> Vec<NodeId> {
+        let mut ids: Vec<_> = self
+            .metadata
+            .iter()
+            .filter(|(id, metadata)| metadata.has_tag(tag) && self.contains_node(**id))
+            .map(|(id, _)| *id)
+            .collect();
+        ids.sort_by_key(|id| id.0);
+        ids
+    }
+
+    /// Gets a mutable reference to the editor metadata of a node, creating it if needed.
+    ///
+    /// # Returns
+    ///
+    /// The metadata of the node, or None if the node doesn't exist
+    pub fn node_metadata_mut(&mut self, id: NodeId) -> Option<&mut NodeMetadata> {
+        if !self.contains_node(id) {
+            return None;
+        }
+        Some(self.metadata.entry(id).or_default())
+    }
 }
 
 #[cfg(test)]
@@ -889,6 +1297,318 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_connection_order() {
+        let graph = create_test_graph();
+
+        // Choices keep the order they were added in
+        let connections = graph.get_connected_nodes(NodeId(2));
+        assert_eq!(connections[0].0, NodeId(3));
+        assert_eq!(connections[1].0, NodeId(4));
+
+        // And survive a serialization round trip
+        let json = serde_json::to_string(&graph).unwrap();
+        let deserialized: DialogueGraph = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.get_connected_nodes(NodeId(2)), connections);
+    }
+
+    #[test]
+    fn test_node_metadata_round_trip() {
+        let mut graph = create_test_graph();
+        let metadata = graph.node_metadata_mut(NodeId(3)).unwrap();
+        metadata.position = Some(Vec2::new(10.0, 20.0));
+        metadata.color = Some("#FFD966".to_string());
+        metadata.comment = Some("Needs a rewrite".to_string());
+        metadata.add_tag("tutorial");
+        graph
+            .node_metadata_mut(NodeId(4))
+            .unwrap()
+            .add_tag("tutorial");
+        assert!(graph.node_metadata_mut(NodeId(99)).is_none());
+
+        let json = serde_json::to_string(&graph).unwrap();
+        let deserialized: DialogueGraph = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            deserialized.node_metadata(NodeId(3)),
+            graph.node_metadata(NodeId(3))
+        );
+        assert!(deserialized.node_metadata(NodeId(1)).is_none());
+        assert_eq!(
+            deserialized.nodes_with_tag("tutorial"),
+            vec![NodeId(3), NodeId(4)]
+        );
+
+        graph.remove_node(NodeId(3)).unwrap();
+        assert!(graph.node_metadata(NodeId(3)).is_none());
+    }
+
+    #[test]
+    fn test_custom_node_round_trip() {
+        let json = r#"{
+            "nodes": [
+                { "type": "Text", "id": 1, "text": "Look!" },
+                { "type": "CameraCut", "id": 2, "data": { "shot": "close_up" } }
+            ],
+            "connections": [{ "from": 1, "to": 2, "label": null }],
+            "start_node": 1,
+            "name": null
+        }"#;
+
+        let graph: DialogueGraph = serde_json::from_str(json).unwrap();
+        match graph.get_node(NodeId(2)) {
+            Some(DialogueNode::Custom {
+                node_type, data, ..
+            }) => {
+                assert_eq!(node_type, "CameraCut");
+                assert_eq!(data["shot"], "close_up");
+            }
+            other => panic!("expected a custom node, got {:?}", other),
+        }
+
+        let json = serde_json::to_string(&graph).unwrap();
+        let deserialized: DialogueGraph = serde_json::from_str(&json).unwrap();
+        assert!(matches!(
+            deserialized.get_node(NodeId(2)),
+            Some(DialogueNode::Custom { .. })
+        ));
+    }
+
+    #[test]
+    fn test_text_variants_round_trip() {
+        let graph = DialogueGraph::new(NodeId(1)).with_node(
+            DialogueNode::text(NodeId(1), "Hello")
+                .with_variants(TextVariantMode::Cycle, ["Hi", "Hey"])
+                .unwrap(),
+        );
+
+        let json = serde_json::to_string(&graph).unwrap();
+        let deserialized: DialogueGraph = serde_json::from_str(&json).unwrap();
+        match deserialized.get_node(NodeId(1)) {
+            Some(DialogueNode::Text {
+                variants,
+                variant_mode,
+                ..
+            }) => {
+                assert_eq!(variants, &["Hi", "Hey"]);
+                assert_eq!(*variant_mode, TextVariantMode::Cycle);
+            }
+            other => panic!("expected a text node, got {:?}", other),
+        }
+
+        // Plain text nodes don't write the variant or timing fields
+        let plain = DialogueGraph::new(NodeId(1)).with_node(DialogueNode::text(NodeId(1), "Hi"));
+        let json = serde_json::to_string(&plain).unwrap();
+        assert!(!json.contains("variant") && !json.contains("advance"));
+    }
+
+    #[test]
+    fn test_timing_round_trip() {
+        let graph = DialogueGraph::new(NodeId(1))
+            .with_node(
+                DialogueNode::text(NodeId(1), "Hold on")
+                    .with_advance(AdvanceTiming::After(0.75))
+                    .unwrap(),
+            )
+            .with_node(DialogueNode::wait(NodeId(2), 2.5));
+
+        let json = serde_json::to_string(&graph).unwrap();
+        let deserialized: DialogueGraph = serde_json::from_str(&json).unwrap();
+        assert!(matches!(
+            deserialized.get_node(NodeId(1)),
+            Some(DialogueNode::Text {
+                advance: AdvanceTiming::After(delay),
+                ..
+            }) if *delay == 0.75
+        ));
+        assert!(matches!(
+            deserialized.get_node(NodeId(2)),
+            Some(DialogueNode::Wait { duration, .. }) if *duration == 2.5
+        ));
+        assert!(!json.contains("timeout"));
+
+        let mut graph = DialogueGraph::new(NodeId(1))
+            .with_node(
+                DialogueNode::choice(NodeId(1))
+                    .with_timeout(3.0, 1)
+                    .unwrap(),
+            )
+            .with_node(DialogueNode::text(NodeId(2), "Yes"));
+        graph
+            .connect(NodeId(1), NodeId(2), ConnectionData::new(None))
+            .unwrap();
+        let invalid = GraphError::InvalidDefaultChoice {
+            node_id: NodeId(1),
+            default_choice: 1,
+            choices: 1,
+        };
+        assert_eq!(graph.validate(), Err(invalid.clone()));
+
+        // Loading doesn't check the default choice, the runner clamps it
+        let json = serde_json::to_string(&graph).unwrap();
+        let deserialized: DialogueGraph = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.validate(), Err(invalid));
+
+        graph.add_node(DialogueNode::text(NodeId(3), "No"));
+        graph
+            .connect(NodeId(1), NodeId(3), ConnectionData::new(None))
+            .unwrap();
+        assert!(graph.validate().is_ok());
+        let json = serde_json::to_string(&graph).unwrap();
+        let deserialized: DialogueGraph = serde_json::from_str(&json).unwrap();
+        assert!(matches!(
+            deserialized.get_node(NodeId(1)),
+            Some(DialogueNode::Choice {
+                timeout: Some(timeout),
+                default_choice: 1,
+                ..
+            }) if *timeout == 3.0
+        ));
+    }
+
+    #[test]
+    fn test_input_node_round_trip() {
+        let json = r#"{
+            "nodes": [
+                {
+                    "type": "Input",
+                    "id": 1,
+                    "prompt": "What's the password?",
+                    "variable": "password",
+                    "rules": ["NotEmpty", { "MaxLength": 16 }]
+                }
+            ],
+            "connections": [],
+            "start_node": 1
+        }"#;
+        let graph: DialogueGraph = serde_json::from_str(json).unwrap();
+        let reserialized: DialogueGraph =
+            serde_json::from_str(&serde_json::to_string(&graph).unwrap()).unwrap();
+
+        for graph in [&graph, &reserialized] {
+            let Some(DialogueNode::Input {
+                prompt,
+                variable,
+                rules,
+                ..
+            }) = graph.get_node(NodeId(1))
+            else {
+                panic!("expected an input node");
+            };
+            assert_eq!(prompt.as_deref(), Some("What's the password?"));
+            assert_eq!(variable, "password");
+            assert_eq!(rules, &vec![InputRule::NotEmpty, InputRule::MaxLength(16)]);
+        }
+    }
+
+    #[test]
+    fn test_hub_round_trip() {
+        let mut graph = DialogueGraph::new(NodeId(1))
+            .with_node(DialogueNode::choice(NodeId(1)).with_hub(true).unwrap())
+            .with_node(DialogueNode::return_to_hub(NodeId(2)));
+        graph
+            .connect(
+                NodeId(1),
+                NodeId(2),
+                ConnectionData::new(None).with_once(true),
+            )
+            .unwrap();
+
+        let json = serde_json::to_string(&graph).unwrap();
+        let deserialized: DialogueGraph = serde_json::from_str(&json).unwrap();
+        assert!(matches!(
+            deserialized.get_node(NodeId(1)),
+            Some(DialogueNode::Choice { hub: true, .. })
+        ));
+        assert!(matches!(
+            deserialized.get_node(NodeId(2)),
+            Some(DialogueNode::Return { .. })
+        ));
+        assert!(deserialized.get_connections(NodeId(1))[0].1.once);
+
+        // Plain choices don't write the flags
+        let graph = DialogueGraph::new(NodeId(1)).with_node(DialogueNode::choice(NodeId(1)));
+        let json = serde_json::to_string(&graph).unwrap();
+        assert!(!json.contains("hub"));
+    }
+
+    #[test]
+    fn test_end_node_round_trip() {
+        let graph = DialogueGraph::new(NodeId(1))
+            .with_node(DialogueNode::end(NodeId(1)).with_outcome("fight").unwrap())
+            .with_node(DialogueNode::end(NodeId(2)));
+
+        let json = serde_json::to_string(&graph).unwrap();
+        let deserialized: DialogueGraph = serde_json::from_str(&json).unwrap();
+        assert!(matches!(
+            deserialized.get_node(NodeId(1)),
+            Some(DialogueNode::End { outcome: Some(outcome), .. }) if outcome == "fight"
+        ));
+        assert!(matches!(
+            deserialized.get_node(NodeId(2)),
+            Some(DialogueNode::End { outcome: None, .. })
+        ));
+    }
+
+    #[test]
+    fn test_entry_points() {
+        let mut graph = DialogueGraph::new(NodeId(1))
+            .with_node(DialogueNode::text(NodeId(1), "Hello, stranger."))
+            .with_node(DialogueNode::text(NodeId(2), "Back again?"))
+            .with_entry_point(EntryPoint::new("returning", NodeId(2)).with_condition("met"));
+
+        // Nodes reachable from an entry point are valid
+        assert!(graph.validate().is_ok());
+
+        let json = serde_json::to_string(&graph).unwrap();
+        let deserialized: DialogueGraph = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.entry_points, graph.entry_points);
+
+        // Adding an entry point with the same name replaces it
+        graph.add_entry_point(EntryPoint::new("returning", NodeId(3)));
+        assert_eq!(graph.entry_points.len(), 1);
+        assert_eq!(
+            graph.validate(),
+            Err(GraphError::MissingEntryNode {
+                name: "returning".to_string(),
+                node_id: NodeId(3),
+            })
+        );
+    }
+
+    #[test]
+    fn test_random_node_round_trip() {
+        let json = r#"{
+            "nodes": [
+                { "type": "Random", "id": 1, "mode": "Shuffle" },
+                { "type": "Text", "id": 2, "text": "A" },
+                { "type": "Text", "id": 3, "text": "B" }
+            ],
+            "connections": [
+                { "from": 1, "to": 2, "label": null, "weight": 3.0, "condition": "met_guide" },
+                { "from": 1, "to": 3, "label": null }
+            ],
+            "start_node": 1,
+            "name": null
+        }"#;
+
+        let graph: DialogueGraph = serde_json::from_str(json).unwrap();
+        let json = serde_json::to_string(&graph).unwrap();
+        let graph: DialogueGraph = serde_json::from_str(&json).unwrap();
+
+        assert!(matches!(
+            graph.get_node(NodeId(1)),
+            Some(DialogueNode::Random {
+                mode: RandomMode::Shuffle,
+                ..
+            })
+        ));
+        let connections = graph.get_connections(NodeId(1));
+        assert_eq!(connections[0].1.weight, 3.0);
+        assert_eq!(connections[0].1.condition.as_deref(), Some("met_guide"));
+        assert_eq!(connections[1].1.weight, 1.0);
+        assert_eq!(connections[1].1.condition, None);
+    }
+
     #[test]
     fn test_node_access() {
         let mut graph = create_test_graph();
@@ -987,6 +1707,32 @@ mod tests {
         graph.validate_mapping().unwrap();
     }
 
+    #[test]
+    fn test_duplicate_connections_by_position() {
+        let mut graph = DialogueGraph::new(NodeId(1))
+            .with_node(DialogueNode::choice(NodeId(1)))
+            .with_node(DialogueNode::text(NodeId(2), "Sure."));
+        for label in ["Yes", "Of course", "Fine"] {
+            graph
+                .connect(NodeId(1), NodeId(2), ConnectionData::new(Some(label.to_string())))
+                .unwrap();
+        }
+
+        graph.get_connection_at_mut(NodeId(1), 1).unwrap().once = true;
+        let removed = graph.remove_connection_at(NodeId(1), 0).unwrap();
+        assert_eq!(removed.label.as_deref(), Some("Yes"));
+
+        let connections = graph.get_connections(NodeId(1));
+        assert_eq!(connections.len(), 2);
+        assert_eq!(connections[0].1.label.as_deref(), Some("Of course"));
+        assert!(connections[0].1.once);
+        assert_eq!(connections[1].1.label.as_deref(), Some("Fine"));
+        assert!(!connections[1].1.once);
+
+        assert!(graph.get_connection_at_mut(NodeId(1), 2).is_none());
+        assert!(graph.remove_connection_at(NodeId(99), 0).is_none());
+    }
+
     #[test]
     fn test_graph_validation() {
         let mut graph = DialogueGraph::new(NodeId(1));
@@ -0,0 +1,237 @@
+//! # Graph export.
+//!
+//! This module renders dialogue graphs as Graphviz DOT and Mermaid diagrams, so the
+//! branching structure of a dialogue can be reviewed without reading its JSON.
+
+use std::fmt::Write;
+
+use super::dialogue_graph::DialogueGraph;
+use super::node::{DialogueElement, NodeId};
+use super::nodes::DialogueNode;
+
+impl DialogueGraph {
+    /// Renders the graph as a Graphviz DOT digraph.
+    ///
+    /// Node labels come from [`DialogueElement::display_name`] and edge labels from
+    /// the connection labels. Text nodes are drawn as boxes, choice nodes as diamonds,
+    /// custom nodes as hexagons, and the start node has a thicker outline.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use funkus_dialogue_core::graph::{DialogueGraph, DialogueNode, NodeId};
+    ///
+    /// let graph = DialogueGraph::new(NodeId(1))
+    ///     .with_node(DialogueNode::text(NodeId(1), "Hello"));
+    /// assert!(graph.to_dot().starts_with("digraph"));
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let mut out = String::new();
+        let name = self.name.as_deref().unwrap_or("dialogue");
+
+        let _ = writeln!(out, "digraph \"{}\" {{", escape_dot(name));
+        let _ = writeln!(out, "    node [fontname=\"Helvetica\"];");
+
+        for node in self.sorted_nodes() {
+            let id = node.id();
+            let shape = match node {
+                DialogueNode::Text { .. } => "box, style=rounded",
+                DialogueNode::Choice { .. } => "diamond",
+                DialogueNode::Random { .. } => "circle",
+                DialogueNode::Wait { .. } => "octagon",
+                DialogueNode::Input { .. } => "parallelogram",
+                DialogueNode::Return { .. } => "invhouse",
+                DialogueNode::End { .. } => "doublecircle",
+                DialogueNode::Custom { .. } => "hexagon",
+            };
+            let start = if id == self.start_node {
+                ", penwidth=3"
+            } else {
+                ""
+            };
+            let _ = writeln!(
+                out,
+                "    n{} [label=\"{:?}\\n{}\", shape={}{}];",
+                id.0,
+                id,
+                escape_dot(&node.display_name()),
+                shape,
+                start
+            );
+        }
+
+        for (from, to, label) in self.sorted_connections() {
+            match label {
+                Some(label) => {
+                    let _ = writeln!(
+                        out,
+                        "    n{} -> n{} [label=\"{}\"];",
+                        from.0,
+                        to.0,
+                        escape_dot(&label)
+                    );
+                }
+                None => {
+                    let _ = writeln!(out, "    n{} -> n{};", from.0, to.0);
+                }
+            }
+        }
+
+        out.push_str("}\n");
+        out
+    }
+
+    /// Renders the graph as a Mermaid flowchart.
+    ///
+    /// Node labels come from [`DialogueElement::display_name`] and edge labels from
+    /// the connection labels. Text nodes are drawn as rectangles, choice nodes as
+    /// rhombi, custom nodes as hexagons, and the start node uses the `start` class.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use funkus_dialogue_core::graph::{DialogueGraph, DialogueNode, NodeId};
+    ///
+    /// let graph = DialogueGraph::new(NodeId(1))
+    ///     .with_node(DialogueNode::text(NodeId(1), "Hello"));
+    /// assert!(graph.to_mermaid().starts_with("flowchart TD"));
+    /// ```
+    pub fn to_mermaid(&self) -> String {
+        let mut out = String::from("flowchart TD\n");
+
+        for node in self.sorted_nodes() {
+            let id = node.id();
+            let label = format!("{:?}: {}", id, escape_mermaid(&node.display_name()));
+            let (shape, class) = match node {
+                DialogueNode::Text { .. } => (format!("[\"{}\"]", label), "text"),
+                DialogueNode::Choice { .. } => (format!("{{\"{}\"}}", label), "choice"),
+                DialogueNode::Random { .. } => (format!("((\"{}\"))", label), "random"),
+                DialogueNode::Wait { .. } => (format!("[/\"{}\"/]", label), "wait"),
+                DialogueNode::Input { .. } => (format!("[\\\"{}\"\\]", label), "input"),
+                DialogueNode::Return { .. } => (format!(">\"{}\"]", label), "return"),
+                // `end` is a keyword in Mermaid, so it can't be a class name
+                DialogueNode::End { .. } => (format!("(((\"{}\")))", label), "endnode"),
+                DialogueNode::Custom { .. } => (format!("{{{{\"{}\"}}}}", label), "custom"),
+            };
+            let _ = writeln!(out, "    n{}{}", id.0, shape);
+            let _ = writeln!(out, "    class n{} {}", id.0, class);
+        }
+
+        for (from, to, label) in self.sorted_connections() {
+            match label {
+                Some(label) => {
+                    let _ = writeln!(
+                        out,
+                        "    n{} -->|\"{}\"| n{}",
+                        from.0,
+                        escape_mermaid(&label),
+                        to.0
+                    );
+                }
+                None => {
+                    let _ = writeln!(out, "    n{} --> n{}", from.0, to.0);
+                }
+            }
+        }
+
+        out.push_str("    classDef text fill:#dbe8f7,stroke:#335c8a\n");
+        out.push_str("    classDef choice fill:#efe0f5,stroke:#6a3d7a\n");
+        out.push_str("    classDef random fill:#fbeed5,stroke:#8a6a33\n");
+        out.push_str("    classDef wait fill:#eeeeee,stroke:#666666\n");
+        out.push_str("    classDef input fill:#fdf6d3,stroke:#8a7d33\n");
+        out.push_str("    classDef return fill:#f7dede,stroke:#8a3333\n");
+        out.push_str("    classDef endnode fill:#dddddd,stroke:#333333\n");
+        out.push_str("    classDef custom fill:#e3f2e1,stroke:#3f7a3d\n");
+        out.push_str("    classDef start stroke-width:4px\n");
+        if self.contains_node(self.start_node) {
+            let _ = writeln!(out, "    class n{} start", self.start_node.0);
+        }
+        out
+    }
+
+    /// Gets all nodes sorted by ID, so exports are stable.
+    fn sorted_nodes(&self) -> Vec<&DialogueNode> {
+        let mut nodes: Vec<_> = self.nodes_iter().collect();
+        nodes.sort_by_key(|node| node.id().0);
+        nodes
+    }
+
+    /// Gets all connections, grouped by source node in ID order.
+    fn sorted_connections(&self) -> Vec<(NodeId, NodeId, Option<String>)> {
+        self.sorted_nodes()
+            .into_iter()
+            .flat_map(|node| {
+                let from = node.id();
+                self.get_connected_nodes(from)
+                    .into_iter()
+                    .map(move |(to, label)| (from, to, label))
+            })
+            .collect()
+    }
+}
+
+/// Escapes a string for use inside a quoted DOT string.
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Escapes a string for use inside a quoted Mermaid label.
+fn escape_mermaid(text: &str) -> String {
+    text.replace('"', "#quot;").replace('\n', "<br/>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::ConnectionData;
+
+    fn create_test_graph() -> DialogueGraph {
+        let mut graph = DialogueGraph::new(NodeId(1))
+            .with_name("Test")
+            .with_node(DialogueNode::text(NodeId(1), "Say \"hi\"").with_speaker("guide"))
+            .with_node(DialogueNode::choice(NodeId(2)))
+            .with_node(DialogueNode::text(NodeId(3), "Bye"));
+        graph
+            .connect(NodeId(1), NodeId(2), ConnectionData::new(None))
+            .unwrap();
+        graph
+            .connect(
+                NodeId(2),
+                NodeId(3),
+                ConnectionData::new(Some("Leave".to_string())),
+            )
+            .unwrap();
+        graph
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let dot = create_test_graph().to_dot();
+
+        assert!(dot.starts_with("digraph \"Test\" {"));
+        assert!(dot.contains("n1 [label=\"NodeId(1)\\nguide: Say \\\"hi\\\"\", shape=box, style=rounded, penwidth=3];"));
+        assert!(dot.contains("n2 [label=\"NodeId(2)\\nChoice\", shape=diamond];"));
+        assert!(dot.contains("n1 -> n2;"));
+        assert!(dot.contains("n2 -> n3 [label=\"Leave\"];"));
+    }
+
+    #[test]
+    fn test_to_mermaid() {
+        let mut graph = create_test_graph();
+        graph.add_node(DialogueNode::end(NodeId(4)));
+        graph
+            .connect(NodeId(3), NodeId(4), ConnectionData::new(None))
+            .unwrap();
+        let mermaid = graph.to_mermaid();
+
+        assert!(mermaid.contains("n1[\"NodeId(1): guide: Say #quot;hi#quot;\"]"));
+        assert!(mermaid.contains("n2{\"NodeId(2): Choice\"}"));
+        assert!(mermaid.contains("n2 -->|\"Leave\"| n3"));
+        assert!(mermaid.contains("class n1 start"));
+        assert!(mermaid.contains("n4(((\"NodeId(4): [End]\")))"));
+        assert!(mermaid.contains("class n4 endnode"));
+        assert!(!mermaid.contains("classDef end "));
+    }
+}
@@ -10,6 +10,7 @@
 //! - Various node types for different dialogue elements
 //! - Connection management between nodes
 //! - Serialization/deserialization support
+//! - Export to Graphviz DOT and Mermaid diagrams
 //!
 //! ## Key Components
 //!
@@ -69,6 +70,7 @@
 /// ```
 
 mod dialogue_graph;
+mod export;
 pub mod node;
 mod nodes;
 
@@ -57,17 +57,180 @@ pub struct Connection {
 ///
 /// This struct represents the properties of a connection between two nodes
 /// in the dialogue graph. It's stored on the edges of the underlying graph.
+///
+/// The weight and condition are only used by random and input nodes, see
+/// [`DialogueNode::Random`](super::DialogueNode::Random). `once` is only used by choice
+/// nodes, see [`DialogueNode::Choice`](super::DialogueNode::Choice).
 #[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
 #[serde(crate = "serde")]
 pub struct ConnectionData {
     /// Optional label for this connection (used as choice text for choice nodes)
     pub label: Option<String>,
+    /// Relative weight of this connection when a random node picks one
+    #[serde(default = "ConnectionData::default_weight")]
+    pub weight: f32,
+    /// Optional condition on the runner variables that must hold for a random node to pick
+    /// this connection
+    #[serde(default)]
+    pub condition: Option<String>,
+    /// Whether this choice is removed from its choice node once it has been taken
+    #[serde(default)]
+    pub once: bool,
 }
 
 impl ConnectionData {
     /// Creates a new connection with an optional label
     pub fn new(label: Option<String>) -> Self {
-        Self { label }
+        Self {
+            label,
+            weight: Self::default_weight(),
+            condition: None,
+            once: false,
+        }
+    }
+
+    /// Builder method to set the weight used by random nodes.
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Builder method to set the condition checked by random nodes.
+    pub fn with_condition(mut self, condition: impl Into<String>) -> Self {
+        self.condition = Some(condition.into());
+        self
+    }
+
+    /// Builder method to make a choice once-only.
+    pub fn with_once(mut self, once: bool) -> Self {
+        self.once = once;
+        self
+    }
+
+    /// Weight of connections that don't set one.
+    pub fn default_weight() -> f32 {
+        1.0
+    }
+}
+
+/// A named node a dialogue can start from, besides its start node.
+///
+/// Entry points let a single dialogue file hold every conversation with an NPC.
+/// A runner can be started from an entry point by name, and entry points with a
+/// condition are picked automatically when it holds as the dialogue starts, see
+/// [`DialogueRunner::start`](crate::DialogueRunner::start).
+///
+/// # Example
+///
+/// ```rust
+/// use funkus_dialogue_core::graph::{DialogueGraph, DialogueNode, EntryPoint, NodeId};
+///
+/// let graph = DialogueGraph::new(NodeId(1))
+///     .with_node(DialogueNode::text(NodeId(1), "Hello, stranger."))
+///     .with_node(DialogueNode::text(NodeId(2), "Did you find my cat?"))
+///     .with_entry_point(EntryPoint::new("quest_active", NodeId(2)).with_condition("cat_quest"));
+///
+/// assert_eq!(graph.entry_point("quest_active").unwrap().node_id, NodeId(2));
+/// ```
+#[derive(Debug, Clone, PartialEq, Reflect, Serialize, Deserialize)]
+#[serde(crate = "serde")]
+pub struct EntryPoint {
+    /// Name the entry point is started by
+    pub name: String,
+    /// Node the dialogue starts from
+    #[serde(rename = "node")]
+    pub node_id: NodeId,
+    /// Condition on the runner variables for picking this entry point automatically
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub condition: Option<String>,
+}
+
+impl EntryPoint {
+    /// Creates a new entry point, only started by name.
+    pub fn new(name: impl Into<String>, node_id: NodeId) -> Self {
+        Self {
+            name: name.into(),
+            node_id,
+            condition: None,
+        }
+    }
+
+    /// Builder method to set the condition picking this entry point automatically.
+    pub fn with_condition(mut self, condition: impl Into<String>) -> Self {
+        self.condition = Some(condition.into());
+        self
+    }
+}
+
+/// Editor data attached to a dialogue node.
+///
+/// Metadata doesn't affect how a dialogue runs. It is stored next to the node in the
+/// graph and serialized in the optional `editor` field of the node, so that tools can
+/// keep layout information and notes in the dialogue file.
+///
+/// Tags can also be queried at runtime with
+/// [`DialogueGraph::nodes_with_tag`](super::DialogueGraph::nodes_with_tag).
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use funkus_dialogue_core::graph::{DialogueGraph, DialogueNode, NodeId};
+///
+/// let mut graph = DialogueGraph::new(NodeId(1))
+///     .with_node(DialogueNode::text(NodeId(1), "Hello"));
+///
+/// let metadata = graph.node_metadata_mut(NodeId(1)).unwrap();
+/// metadata.position = Some(Vec2::new(40.0, 80.0));
+/// metadata.add_tag("tutorial");
+///
+/// assert_eq!(graph.nodes_with_tag("tutorial"), vec![NodeId(1)]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Reflect, Serialize, Deserialize)]
+#[serde(crate = "serde")]
+pub struct NodeMetadata {
+    /// Position of the node in the editor canvas
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<Vec2>,
+    /// Optional hex color of the node in the editor (e.g. `"#FFD966"`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// Optional writer comment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// Free-form tags
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl NodeMetadata {
+    /// Checks if no metadata is set.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Checks if the node has the given tag.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /// Adds a tag, unless the node already has it.
+    pub fn add_tag(&mut self, tag: impl Into<String>) {
+        let tag = tag.into();
+        if !self.has_tag(&tag) {
+            self.tags.push(tag);
+        }
+    }
+
+    /// Removes a tag.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the node had the tag, `false` otherwise
+    pub fn remove_tag(&mut self, tag: &str) -> bool {
+        let len = self.tags.len();
+        self.tags.retain(|t| t != tag);
+        self.tags.len() != len
     }
 }
 
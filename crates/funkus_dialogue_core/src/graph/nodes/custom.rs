@@ -0,0 +1,237 @@
+//! # Custom node types.
+//!
+//! Games can add their own node types, such as camera cuts or minigames, by
+//! implementing [`CustomDialogueNode`] and registering the type with
+//! [`DialogueNodeAppExt::register_dialogue_node`].
+//!
+//! Custom nodes are stored in the graph as [`DialogueNode::Custom`](super::DialogueNode::Custom)
+//! with their type name and raw data, so dialogue files can be loaded, edited and saved
+//! without knowing the node types of the game:
+//!
+//! ```json
+//! { "type": "CameraCut", "id": 4, "data": { "shot": "close_up" } }
+//! ```
+//!
+//! When a runner enters a custom node, the registered type is deserialized from the data,
+//! its [`on_enter`](CustomDialogueNode::on_enter) hook runs with access to the `World`, and
+//! its [`advance_policy`](CustomDialogueNode::advance_policy) decides whether the dialogue
+//! moves on by itself.
+
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use crate::error::{DialogueError, DialogueResult};
+use crate::graph::node::{DialogueElement, NodeId};
+
+/// How a dialogue continues after entering a custom node.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+pub enum CustomNodeAdvance {
+    /// Continue to the next node right after the node was entered
+    Immediate,
+    /// Stay in the `Waiting` state until an `AdvanceDialogue` event is sent
+    #[default]
+    WaitForAdvance,
+}
+
+/// Trait for game-specific dialogue node types.
+///
+/// A custom node is created from the `data` of a node whose `type` matches
+/// [`type_name`](Self::type_name) every time a runner enters it.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use funkus_dialogue_core::graph::{CustomDialogueNode, CustomNodeAdvance, DialogueElement, NodeId};
+/// use funkus_dialogue_core::DialogueNodeAppExt;
+/// use serde::Deserialize;
+///
+/// struct CameraCut {
+///     id: NodeId,
+///     shot: String,
+/// }
+///
+/// impl DialogueElement for CameraCut {
+///     fn id(&self) -> NodeId {
+///         self.id
+///     }
+///
+///     fn display_name(&self) -> String {
+///         format!("Camera cut: {}", self.shot)
+///     }
+/// }
+///
+/// impl CustomDialogueNode for CameraCut {
+///     fn type_name() -> &'static str {
+///         "CameraCut"
+///     }
+///
+///     fn from_data(id: NodeId, data: &serde_json::Value) -> Result<Self, serde_json::Error> {
+///         let shot = String::deserialize(&data["shot"])?;
+///         Ok(Self { id, shot })
+///     }
+///
+///     fn on_enter(&self, _entity: Entity, world: &mut World) {
+///         info!("Cutting to {}", self.shot);
+///         // e.g. world.resource_mut::<CameraDirector>().cut_to(&self.shot);
+///     }
+///
+///     fn advance_policy(&self) -> CustomNodeAdvance {
+///         CustomNodeAdvance::Immediate
+///     }
+/// }
+///
+/// fn build(app: &mut App) {
+///     app.register_dialogue_node::<CameraCut>();
+/// }
+/// ```
+pub trait CustomDialogueNode: DialogueElement {
+    /// Name of the node type, as written in the `type` field of dialogue files.
+    fn type_name() -> &'static str
+    where
+        Self: Sized;
+
+    /// Creates the node from the `data` field of a dialogue node.
+    fn from_data(id: NodeId, data: &serde_json::Value) -> Result<Self, serde_json::Error>
+    where
+        Self: Sized;
+
+    /// Called when a runner enters the node.
+    ///
+    /// The hook runs as a command, after the system that moved the runner.
+    fn on_enter(&self, _entity: Entity, _world: &mut World) {}
+
+    /// Decides how the dialogue continues after entering the node.
+    fn advance_policy(&self) -> CustomNodeAdvance {
+        CustomNodeAdvance::WaitForAdvance
+    }
+}
+
+/// Function creating a registered custom node from its data.
+type CustomNodeFactory =
+    fn(NodeId, &serde_json::Value) -> Result<Box<dyn CustomDialogueNode>, serde_json::Error>;
+
+/// Resource holding the registered custom node types.
+///
+/// Types are usually registered with [`DialogueNodeAppExt::register_dialogue_node`].
+#[derive(Resource, Default)]
+pub struct DialogueNodeRegistry {
+    factories: HashMap<String, CustomNodeFactory>,
+}
+
+impl DialogueNodeRegistry {
+    /// Registers a custom node type, replacing any type registered under the same name.
+    pub fn register<T: CustomDialogueNode>(&mut self) {
+        self.factories
+            .insert(T::type_name().to_string(), create_node::<T>);
+    }
+
+    /// Checks if a node type is registered.
+    pub fn is_registered(&self, node_type: &str) -> bool {
+        self.factories.contains_key(node_type)
+    }
+
+    /// Creates a custom node of a registered type from its data.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UnknownNodeType` if no type is registered under this name, or
+    /// `InvalidNodeData` if the data doesn't match the registered type
+    pub fn create(
+        &self,
+        id: NodeId,
+        node_type: &str,
+        data: &serde_json::Value,
+    ) -> DialogueResult<Box<dyn CustomDialogueNode>> {
+        let factory =
+            self.factories
+                .get(node_type)
+                .ok_or_else(|| DialogueError::UnknownNodeType {
+                    node_id: id,
+                    node_type: node_type.to_string(),
+                })?;
+
+        factory(id, data).map_err(|err| DialogueError::InvalidNodeData {
+            node_id: id,
+            message: err.to_string(),
+        })
+    }
+}
+
+fn create_node<T: CustomDialogueNode>(
+    id: NodeId,
+    data: &serde_json::Value,
+) -> Result<Box<dyn CustomDialogueNode>, serde_json::Error> {
+    Ok(Box::new(T::from_data(id, data)?))
+}
+
+/// Extension trait to register custom node types on an [`App`].
+pub trait DialogueNodeAppExt {
+    /// Registers a custom node type in the [`DialogueNodeRegistry`].
+    fn register_dialogue_node<T: CustomDialogueNode>(&mut self) -> &mut Self;
+}
+
+impl DialogueNodeAppExt for App {
+    fn register_dialogue_node<T: CustomDialogueNode>(&mut self) -> &mut Self {
+        self.init_resource::<DialogueNodeRegistry>();
+        self.world_mut()
+            .resource_mut::<DialogueNodeRegistry>()
+            .register::<T>();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    struct Shake {
+        id: NodeId,
+        strength: f32,
+    }
+
+    impl DialogueElement for Shake {
+        fn id(&self) -> NodeId {
+            self.id
+        }
+
+        fn display_name(&self) -> String {
+            format!("Shake {}", self.strength)
+        }
+    }
+
+    impl CustomDialogueNode for Shake {
+        fn type_name() -> &'static str {
+            "Shake"
+        }
+
+        fn from_data(id: NodeId, data: &serde_json::Value) -> Result<Self, serde_json::Error> {
+            let strength = f32::deserialize(&data["strength"])?;
+            Ok(Self { id, strength })
+        }
+    }
+
+    #[test]
+    fn test_registry_creates_nodes() {
+        let mut registry = DialogueNodeRegistry::default();
+        registry.register::<Shake>();
+        assert!(registry.is_registered("Shake"));
+
+        let node = registry
+            .create(NodeId(3), "Shake", &serde_json::json!({ "strength": 0.5 }))
+            .unwrap();
+        assert_eq!(node.id(), NodeId(3));
+        assert_eq!(node.display_name(), "Shake 0.5");
+        assert_eq!(node.advance_policy(), CustomNodeAdvance::WaitForAdvance);
+
+        assert!(matches!(
+            registry.create(NodeId(3), "Shake", &serde_json::json!({ "strength": "x" })),
+            Err(DialogueError::InvalidNodeData { .. })
+        ));
+        assert!(matches!(
+            registry.create(NodeId(3), "Fade", &serde_json::Value::Null),
+            Err(DialogueError::UnknownNodeType { .. })
+        ));
+    }
+}
@@ -0,0 +1,95 @@
+//! # Validation of free-form player input.
+//!
+//! Input nodes ask the player for a string, such as a name or a password, and
+//! check it against a list of [`InputRule`]s before storing it into a variable.
+//!
+//! ```json
+//! {
+//!   "type": "Input",
+//!   "id": 3,
+//!   "prompt": "What's the password?",
+//!   "variable": "password",
+//!   "rules": ["NotEmpty", { "MaxLength": 16 }]
+//! }
+//! ```
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A rule the input of an input node must follow.
+///
+/// Rules are checked on the trimmed input.
+#[derive(Debug, Clone, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+#[serde(crate = "serde")]
+pub enum InputRule {
+    /// The input must not be empty
+    NotEmpty,
+    /// The input must have at least this many characters
+    MinLength(usize),
+    /// The input must have at most this many characters
+    MaxLength(usize),
+    /// The input may only contain letters and digits
+    Alphanumeric,
+    /// The input may only contain digits
+    Numeric,
+    /// The input must be one of these values, ignoring case
+    OneOf(Vec<String>),
+}
+
+impl InputRule {
+    /// Checks if an input follows this rule.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use funkus_dialogue_core::graph::InputRule;
+    ///
+    /// assert!(InputRule::MaxLength(5).accepts("Funk"));
+    /// assert!(!InputRule::Numeric.accepts("12a"));
+    /// ```
+    pub fn accepts(&self, input: &str) -> bool {
+        let input = input.trim();
+        match self {
+            InputRule::NotEmpty => !input.is_empty(),
+            InputRule::MinLength(length) => input.chars().count() >= *length,
+            InputRule::MaxLength(length) => input.chars().count() <= *length,
+            InputRule::Alphanumeric => input.chars().all(char::is_alphanumeric),
+            InputRule::Numeric => input.chars().all(|c| c.is_ascii_digit()),
+            InputRule::OneOf(values) => {
+                values.iter().any(|value| value.eq_ignore_ascii_case(input))
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for InputRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputRule::NotEmpty => write!(f, "must not be empty"),
+            InputRule::MinLength(length) => write!(f, "must have at least {} characters", length),
+            InputRule::MaxLength(length) => write!(f, "must have at most {} characters", length),
+            InputRule::Alphanumeric => write!(f, "may only contain letters and digits"),
+            InputRule::Numeric => write!(f, "may only contain digits"),
+            InputRule::OneOf(values) => write!(f, "must be one of {}", values.join(", ")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_rules() {
+        assert!(!InputRule::NotEmpty.accepts("   "));
+        assert!(InputRule::MinLength(3).accepts(" Ada "));
+        assert!(!InputRule::MinLength(4).accepts(" Ada "));
+        assert!(InputRule::Alphanumeric.accepts("R2D2"));
+        assert!(!InputRule::Alphanumeric.accepts("R2-D2"));
+        assert!(InputRule::OneOf(vec!["Swordfish".into()]).accepts("swordfish"));
+
+        let rules: Vec<InputRule> =
+            serde_json::from_str(r#"["NotEmpty", { "MaxLength": 16 }]"#).unwrap();
+        assert_eq!(rules, vec![InputRule::NotEmpty, InputRule::MaxLength(16)]);
+    }
+}
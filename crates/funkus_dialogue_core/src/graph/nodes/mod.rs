@@ -6,8 +6,16 @@
 //! 
 //! The dialogue system supports these node types:
 //! 
-//! - **Text Nodes**: Display narrative text with speaker information
-//! - **Choice Nodes**: Present options to the player
+//! - **Text Nodes**: Display narrative text with speaker information, optionally
+//!   varying the line each time the node is shown
+//! - **Choice Nodes**: Present options to the player, optionally as a hub whose
+//!   once-only options are removed after they have been taken
+//! - **Random Nodes**: Pick one of their connections automatically
+//! - **Wait Nodes**: Pause the dialogue for a duration
+//! - **Input Nodes**: Ask the player for a string, see [`InputRule`]
+//! - **Return Nodes**: Go back to the last hub choice node
+//! - **End Nodes**: End the dialogue with an outcome
+//! - **Custom Nodes**: Game-specific nodes, see [`CustomDialogueNode`]
 //! 
 //! Additional node types planned for future versions:
 //! 
@@ -20,6 +28,12 @@ use serde::{Serialize, Deserialize};
 
 use super::node::{DialogueElement, NodeId};
 
+mod custom;
+mod input;
+
+pub use custom::*;
+pub use input::*;
+
 /// Enum containing all supported node types.
 /// 
 /// DialogueNode is the core representation of different node types in the dialogue system.
@@ -31,6 +45,12 @@ use super::node::{DialogueElement, NodeId};
 /// 
 /// * `Text` - Node that displays text from a speaker
 /// * `Choice` - Node that presents choices to the player
+/// * `Random` - Node that picks one of its connections automatically
+/// * `Wait` - Node that pauses the dialogue for a duration
+/// * `Input` - Node that asks the player for a string
+/// * `Return` - Node that goes back to the last hub choice node
+/// * `End` - Node that ends the dialogue with an outcome
+/// * `Custom` - Game-specific node, handled by a registered [`CustomDialogueNode`]
 /// 
 /// # Example
 /// 
@@ -53,28 +73,186 @@ use super::node::{DialogueElement, NodeId};
 #[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
 #[serde(crate = "serde")]
 pub enum DialogueNode {
-    /// Node that displays text from a speaker
+    /// Node that displays text from a speaker.
+    ///
+    /// A text node can hold alternative lines in `variants`. The runner shows `text`
+    /// and the variants in turn, as decided by `variant_mode`.
     Text {
         /// Unique identifier for this node
         id: NodeId,
         /// The text content to display
         text: String,
-        /// The name of the speaker (optional)
+        /// Alternative lines, shown after `text` according to `variant_mode`
+        variants: Vec<String>,
+        /// How the line shown is picked among `text` and the variants
+        variant_mode: TextVariantMode,
+        /// When the dialogue moves on from this node by itself
+        advance: AdvanceTiming,
+        /// The speaker ID, usually a key into the character database (optional)
         speaker: Option<String>,
         /// Optional portrait or avatar identifier for the speaker
         portrait: Option<String>,
     },
-    /// Node that presents choices to the player
+    /// Node that presents choices to the player.
+    ///
+    /// Choices are the node's connections, minus the once-only connections that were
+    /// already taken (see [`ConnectionData::once`](super::ConnectionData::once)). A node
+    /// whose choices were all taken is left as if a `Return` node was entered.
+    ///
+    /// A hub node is a menu the dialogue keeps coming back to: `Return` nodes lead
+    /// back to the last hub the runner entered.
     Choice {
         /// Unique identifier for this node
         id: NodeId,
         /// Optional prompt text to display before the choices
         prompt: Option<String>,
-        /// Optional speaker for the prompt
+        /// Seconds to wait for a choice before taking `default_choice`, if any
+        timeout: Option<f32>,
+        /// Index of the choice taken when the timeout runs out
+        default_choice: usize,
+        /// Whether `Return` nodes lead back to this node
+        hub: bool,
+        /// Optional speaker ID for the prompt
         speaker: Option<String>,
         /// Optional portrait or avatar identifier for the speaker
         portrait: Option<String>,
     },
+    /// Node that picks one of its connections automatically.
+    ///
+    /// Connections are picked according to their weight, among those whose condition
+    /// holds. The runner moves through random nodes without stopping on them.
+    Random {
+        /// Unique identifier for this node
+        id: NodeId,
+        /// How connections are picked
+        mode: RandomMode,
+    },
+    /// Node that pauses the dialogue, then moves on to its next node.
+    ///
+    /// Sending `AdvanceDialogue` while waiting skips the rest of the wait.
+    Wait {
+        /// Unique identifier for this node
+        id: NodeId,
+        /// Time to wait, in seconds
+        duration: f32,
+    },
+    /// Node that asks the player for a string and stores it into a variable.
+    ///
+    /// The input must follow every rule of the node. Once stored, the runner follows
+    /// the first connection whose condition holds, so connections can branch on the
+    /// input, e.g. with `password == swordfish`.
+    Input {
+        /// Unique identifier for this node
+        id: NodeId,
+        /// Optional prompt text to display above the input
+        prompt: Option<String>,
+        /// Name of the runner variable the input is stored into
+        variable: String,
+        /// Rules the input must follow
+        rules: Vec<InputRule>,
+        /// Optional speaker ID for the prompt
+        speaker: Option<String>,
+        /// Optional portrait or avatar identifier for the speaker
+        portrait: Option<String>,
+    },
+    /// Node that goes back to the last hub choice node the runner entered.
+    ///
+    /// Hubs without any choice left are skipped in favor of the hub entered before
+    /// them. The dialogue finishes if there is no hub to go back to. Like random
+    /// nodes, return nodes are passed through without stopping.
+    Return {
+        /// Unique identifier for this node
+        id: NodeId,
+    },
+    /// Node that ends the dialogue.
+    ///
+    /// The outcome is passed on to `DialogueEnded`, so the game can tell how the
+    /// dialogue ended, e.g. `quest_accepted` or `fight`. Dialogues also end without
+    /// an outcome on nodes that lead nowhere.
+    End {
+        /// Unique identifier for this node
+        id: NodeId,
+        /// How the dialogue ended, if it matters to the game
+        outcome: Option<String>,
+    },
+    /// Game-specific node, handled by the [`CustomDialogueNode`] registered for its type
+    Custom {
+        /// Unique identifier for this node
+        id: NodeId,
+        /// Name of the node type, as registered in the [`DialogueNodeRegistry`]
+        node_type: String,
+        /// Node data, deserialized by the registered node type
+        #[reflect(ignore)]
+        data: serde_json::Value,
+    },
+}
+
+/// How a random node picks its connections.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+#[serde(crate = "serde")]
+pub enum RandomMode {
+    /// Every pick is independent, according to the connection weights
+    #[default]
+    Weighted,
+    /// Connections aren't picked again until every other one has been picked
+    Shuffle,
+    /// Connections are picked in order, starting over after the last one
+    Cycle,
+}
+
+/// How a text node picks the line it shows among its text and variants.
+///
+/// Lines are numbered from the node's `text` (line 0) followed by its variants.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+#[serde(crate = "serde")]
+pub enum TextVariantMode {
+    /// Lines are shown in order, the last one is repeated afterwards
+    #[default]
+    Sequence,
+    /// Lines are shown in order, starting over after the last one
+    Cycle,
+    /// Lines are shown in a random order, without repeats until every line was shown
+    Shuffle,
+    /// Lines are shown in order, once each. The node is then passed through
+    /// as if it had been advanced.
+    Once,
+}
+
+/// When the dialogue moves on from a text node by itself.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Reflect, Serialize, Deserialize)]
+#[serde(crate = "serde")]
+pub enum AdvanceTiming {
+    /// Use the `auto_advance` settings of the runner
+    #[default]
+    Runner,
+    /// Move on after this many seconds, even if the runner doesn't auto-advance
+    After(f32),
+    /// Wait for an `AdvanceDialogue` event, even if the runner auto-advances
+    WaitForSignal,
+}
+
+/// The kind of a dialogue node, without its data.
+///
+/// Useful for reacting to a node type without matching on the whole [`DialogueNode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+#[serde(crate = "serde")]
+pub enum DialogueNodeKind {
+    /// A [`DialogueNode::Text`] node
+    Text,
+    /// A [`DialogueNode::Choice`] node
+    Choice,
+    /// A [`DialogueNode::Random`] node
+    Random,
+    /// A [`DialogueNode::Wait`] node
+    Wait,
+    /// A [`DialogueNode::Input`] node
+    Input,
+    /// A [`DialogueNode::Return`] node
+    Return,
+    /// A [`DialogueNode::End`] node
+    End,
+    /// A [`DialogueNode::Custom`] node
+    Custom,
 }
 
 impl DialogueNode {
@@ -100,6 +278,9 @@ impl DialogueNode {
         DialogueNode::Text {
             id,
             text: text.into(),
+            variants: Vec::new(),
+            variant_mode: TextVariantMode::default(),
+            advance: AdvanceTiming::default(),
             speaker: None,
             portrait: None,
         }
@@ -126,14 +307,119 @@ impl DialogueNode {
         DialogueNode::Choice {
             id,
             prompt: None,
+            timeout: None,
+            default_choice: 0,
+            hub: false,
+            speaker: None,
+            portrait: None,
+        }
+    }
+    
+    /// Creates a new random node with the given ID and mode.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use funkus_dialogue_core::graph::{DialogueNode, NodeId, RandomMode};
+    /// 
+    /// let node = DialogueNode::random(NodeId(5), RandomMode::Shuffle);
+    /// ```
+    pub fn random(id: NodeId, mode: RandomMode) -> Self {
+        DialogueNode::Random { id, mode }
+    }
+    
+    /// Creates a new wait node with the given ID and duration in seconds.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use funkus_dialogue_core::graph::{DialogueNode, NodeId};
+    /// 
+    /// // Let the camera pan for a second and a half
+    /// let node = DialogueNode::wait(NodeId(6), 1.5);
+    /// ```
+    pub fn wait(id: NodeId, duration: f32) -> Self {
+        DialogueNode::Wait { id, duration }
+    }
+    
+    /// Creates a new input node storing the input into the given variable.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use funkus_dialogue_core::graph::{DialogueNode, InputRule, NodeId};
+    /// 
+    /// let node = DialogueNode::input(NodeId(3), "player_name")
+    ///     .with_prompt("What's your name?").unwrap()
+    ///     .with_rules([InputRule::NotEmpty, InputRule::MaxLength(12)]).unwrap();
+    /// ```
+    pub fn input(id: NodeId, variable: impl Into<String>) -> Self {
+        DialogueNode::Input {
+            id,
+            prompt: None,
+            variable: variable.into(),
+            rules: Vec::new(),
             speaker: None,
             portrait: None,
         }
     }
     
+    /// Creates a new node going back to the last hub choice node.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use funkus_dialogue_core::graph::{DialogueNode, NodeId};
+    /// 
+    /// let node = DialogueNode::return_to_hub(NodeId(9));
+    /// ```
+    pub fn return_to_hub(id: NodeId) -> Self {
+        DialogueNode::Return { id }
+    }
+    
+    /// Creates a new node ending the dialogue, without an outcome.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use funkus_dialogue_core::graph::{DialogueNode, NodeId};
+    /// 
+    /// let node = DialogueNode::end(NodeId(10)).with_outcome("quest_accepted").unwrap();
+    /// ```
+    pub fn end(id: NodeId) -> Self {
+        DialogueNode::End { id, outcome: None }
+    }
+    
+    /// Creates a new custom node with the given ID, type and data.
+    /// 
+    /// # Parameters
+    /// 
+    /// * `id` - Unique identifier for this node
+    /// * `node_type` - Name of a node type registered in the [`DialogueNodeRegistry`]
+    /// * `data` - Data of the node, deserialized by the registered node type
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use funkus_dialogue_core::graph::{DialogueNode, NodeId};
+    /// 
+    /// let node = DialogueNode::custom(
+    ///     NodeId(3),
+    ///     "CameraCut",
+    ///     serde_json::json!({ "shot": "close_up" }),
+    /// );
+    /// ```
+    pub fn custom(id: NodeId, node_type: impl Into<String>, data: serde_json::Value) -> Self {
+        DialogueNode::Custom {
+            id,
+            node_type: node_type.into(),
+            data,
+        }
+    }
+    
     /// Sets the speaker for this node.
     /// 
-    /// This method can be used with any node type to set the speaker.
+    /// This method can be used with text and choice nodes; other nodes have no speaker.
     /// 
     /// # Parameters
     /// 
@@ -151,12 +437,18 @@ impl DialogueNode {
         match self {
             DialogueNode::Text { speaker: s, .. } => *s = Some(speaker.into()),
             DialogueNode::Choice { speaker: s, .. } => *s = Some(speaker.into()),
+            DialogueNode::Input { speaker: s, .. } => *s = Some(speaker.into()),
+            DialogueNode::Random { .. }
+            | DialogueNode::Wait { .. }
+            | DialogueNode::Return { .. }
+            | DialogueNode::End { .. }
+            | DialogueNode::Custom { .. } => {}
         }
     }
     
     /// Sets the portrait for this node.
     /// 
-    /// This method can be used with any node type to set the portrait.
+    /// This method can be used with text and choice nodes; other nodes have no portrait.
     /// 
     /// # Parameters
     /// 
@@ -174,6 +466,12 @@ impl DialogueNode {
         match self {
             DialogueNode::Text { portrait: p, .. } => *p = Some(portrait.into()),
             DialogueNode::Choice { portrait: p, .. } => *p = Some(portrait.into()),
+            DialogueNode::Input { portrait: p, .. } => *p = Some(portrait.into()),
+            DialogueNode::Random { .. }
+            | DialogueNode::Wait { .. }
+            | DialogueNode::Return { .. }
+            | DialogueNode::End { .. }
+            | DialogueNode::Custom { .. } => {}
         }
     }
     
@@ -205,7 +503,7 @@ impl DialogueNode {
         }
     }
     
-    /// Sets the prompt for a Choice node.
+    /// Sets the prompt for a Choice or Input node.
     /// 
     /// # Parameters
     /// 
@@ -213,7 +511,7 @@ impl DialogueNode {
     /// 
     /// # Returns
     /// 
-    /// Ok(()) if successful, or an error if this is not a Choice node
+    /// Ok(()) if successful, or an error if this is not a Choice or Input node
     /// 
     /// # Example
     /// 
@@ -225,11 +523,183 @@ impl DialogueNode {
     /// ```
     pub fn set_prompt(&mut self, prompt: impl Into<String>) -> Result<(), &'static str> {
         match self {
-            DialogueNode::Choice { prompt: p, .. } => {
+            DialogueNode::Choice { prompt: p, .. } | DialogueNode::Input { prompt: p, .. } => {
                 *p = Some(prompt.into());
                 Ok(())
             }
-            _ => Err("Can only set prompt on a Choice node"),
+            _ => Err("Can only set prompt on a Choice or Input node"),
+        }
+    }
+    
+    /// Builder method to set the line variants of a Text node.
+    /// 
+    /// # Parameters
+    /// 
+    /// * `mode` - How the line shown is picked
+    /// * `variants` - Alternative lines, shown after the node text
+    /// 
+    /// # Returns
+    /// 
+    /// A Result containing the node with the variants set if successful,
+    /// or an error message if this is not a Text node
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use funkus_dialogue_core::graph::{DialogueNode, NodeId, TextVariantMode};
+    /// 
+    /// let node = DialogueNode::text(NodeId(1), "Hello, stranger.")
+    ///     .with_variants(TextVariantMode::Sequence, ["Back again?", "You again."])
+    ///     .unwrap();
+    /// ```
+    pub fn with_variants<I, S>(
+        mut self,
+        mode: TextVariantMode,
+        lines: I,
+    ) -> Result<Self, &'static str>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        match &mut self {
+            DialogueNode::Text {
+                variants,
+                variant_mode,
+                ..
+            } => {
+                *variants = lines.into_iter().map(Into::into).collect();
+                *variant_mode = mode;
+                Ok(self)
+            }
+            _ => Err("Can only set variants on a Text node"),
+        }
+    }
+    
+    /// Builder method to set the validation rules of an Input node.
+    /// 
+    /// # Returns
+    /// 
+    /// A Result containing the node with the rules set if successful,
+    /// or an error message if this is not an Input node
+    pub fn with_rules(
+        mut self,
+        input_rules: impl IntoIterator<Item = InputRule>,
+    ) -> Result<Self, &'static str> {
+        match &mut self {
+            DialogueNode::Input { rules, .. } => {
+                *rules = input_rules.into_iter().collect();
+                Ok(self)
+            }
+            _ => Err("Can only set rules on an Input node"),
+        }
+    }
+    
+    /// Builder method to give a Choice node a time limit.
+    /// 
+    /// # Parameters
+    /// 
+    /// * `seconds` - Time the player has to choose
+    /// * `default_choice` - Index of the choice taken when the time runs out
+    /// 
+    /// # Returns
+    /// 
+    /// A Result containing the node with the timeout set if successful,
+    /// or an error message if this is not a Choice node
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use funkus_dialogue_core::graph::{DialogueNode, NodeId};
+    /// 
+    /// // Stay silent if the player doesn't answer within five seconds
+    /// let node = DialogueNode::choice(NodeId(2))
+    ///     .with_timeout(5.0, 2)
+    ///     .unwrap();
+    /// ```
+    pub fn with_timeout(mut self, seconds: f32, default: usize) -> Result<Self, &'static str> {
+        match &mut self {
+            DialogueNode::Choice {
+                timeout,
+                default_choice,
+                ..
+            } => {
+                *timeout = Some(seconds);
+                *default_choice = default;
+                Ok(self)
+            }
+            _ => Err("Can only set a timeout on a Choice node"),
+        }
+    }
+    
+    /// Builder method to make a Choice node a hub that `Return` nodes lead back to.
+    /// 
+    /// # Returns
+    /// 
+    /// A Result containing the node with the hub flag set if successful,
+    /// or an error message if this is not a Choice node
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use funkus_dialogue_core::graph::{DialogueNode, NodeId};
+    /// 
+    /// let node = DialogueNode::choice(NodeId(2))
+    ///     .with_prompt("What would you like to know?").unwrap()
+    ///     .with_hub(true).unwrap();
+    /// ```
+    pub fn with_hub(mut self, is_hub: bool) -> Result<Self, &'static str> {
+        match &mut self {
+            DialogueNode::Choice { hub, .. } => {
+                *hub = is_hub;
+                Ok(self)
+            }
+            _ => Err("Can only make a Choice node a hub"),
+        }
+    }
+    
+    /// Builder method to set the outcome of an End node.
+    /// 
+    /// # Returns
+    /// 
+    /// A Result containing the node with the outcome set if successful,
+    /// or an error message if this is not an End node
+    pub fn with_outcome(mut self, end_outcome: impl Into<String>) -> Result<Self, &'static str> {
+        match &mut self {
+            DialogueNode::End { outcome, .. } => {
+                *outcome = Some(end_outcome.into());
+                Ok(self)
+            }
+            _ => Err("Can only set an outcome on an End node"),
+        }
+    }
+    
+    /// Builder method to set when a Text node moves on by itself.
+    /// 
+    /// # Parameters
+    /// 
+    /// * `timing` - When the dialogue moves on from the node
+    /// 
+    /// # Returns
+    /// 
+    /// A Result containing the node with the timing set if successful,
+    /// or an error message if this is not a Text node
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use funkus_dialogue_core::graph::{AdvanceTiming, DialogueNode, NodeId};
+    /// 
+    /// let node = DialogueNode::text(NodeId(1), "Look out!")
+    ///     .with_advance(AdvanceTiming::After(0.8))
+    ///     .unwrap();
+    /// ```
+    pub fn with_advance(mut self, timing: AdvanceTiming) -> Result<Self, &'static str> {
+        match &mut self {
+            DialogueNode::Text { advance, .. } => {
+                *advance = timing;
+                Ok(self)
+            }
+            _ => Err("Can only set advance timing on a Text node"),
         }
     }
     
@@ -279,7 +749,7 @@ impl DialogueNode {
         self
     }
     
-    /// Builder method to set the prompt for a Choice node.
+    /// Builder method to set the prompt for a Choice or Input node.
     /// 
     /// # Parameters
     /// 
@@ -288,7 +758,7 @@ impl DialogueNode {
     /// # Returns
     /// 
     /// A Result containing the node with the prompt set if successful,
-    /// or an error message if this is not a Choice node
+    /// or an error message if this is not a Choice or Input node
     /// 
     /// # Example
     /// 
@@ -302,6 +772,98 @@ impl DialogueNode {
         self.set_prompt(prompt)?;
         Ok(self)
     }
+    
+    /// Gets the speaker ID of this node, if any.
+    /// 
+    /// The speaker is usually a key into a [`CharacterDatabase`](crate::CharacterDatabase),
+    /// but free-form names are also supported.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use funkus_dialogue::graph::{DialogueNode, NodeId};
+    /// 
+    /// let node = DialogueNode::text(NodeId(1), "Hello")
+    ///     .with_speaker("guide");
+    /// assert_eq!(node.speaker(), Some("guide"));
+    /// ```
+    pub fn speaker(&self) -> Option<&str> {
+        match self {
+            DialogueNode::Text { speaker, .. }
+            | DialogueNode::Choice { speaker, .. }
+            | DialogueNode::Input { speaker, .. } => speaker.as_deref(),
+            DialogueNode::Random { .. }
+            | DialogueNode::Wait { .. }
+            | DialogueNode::Return { .. }
+            | DialogueNode::End { .. }
+            | DialogueNode::Custom { .. } => None,
+        }
+    }
+    
+    /// Gets the portrait identifier of this node, if any.
+    pub fn portrait(&self) -> Option<&str> {
+        match self {
+            DialogueNode::Text { portrait, .. }
+            | DialogueNode::Choice { portrait, .. }
+            | DialogueNode::Input { portrait, .. } => portrait.as_deref(),
+            DialogueNode::Random { .. }
+            | DialogueNode::Wait { .. }
+            | DialogueNode::Return { .. }
+            | DialogueNode::End { .. }
+            | DialogueNode::Custom { .. } => None,
+        }
+    }
+
+    /// Gets the kind of this node.
+    pub fn kind(&self) -> DialogueNodeKind {
+        match self {
+            DialogueNode::Text { .. } => DialogueNodeKind::Text,
+            DialogueNode::Choice { .. } => DialogueNodeKind::Choice,
+            DialogueNode::Random { .. } => DialogueNodeKind::Random,
+            DialogueNode::Wait { .. } => DialogueNodeKind::Wait,
+            DialogueNode::Input { .. } => DialogueNodeKind::Input,
+            DialogueNode::Return { .. } => DialogueNodeKind::Return,
+            DialogueNode::End { .. } => DialogueNodeKind::End,
+            DialogueNode::Custom { .. } => DialogueNodeKind::Custom,
+        }
+    }
+
+    /// Gets a line of a Text node: its text for index 0, then its variants.
+    /// 
+    /// # Returns
+    /// 
+    /// The line, or None if this is not a Text node or the index is out of range
+    pub fn line(&self, index: usize) -> Option<&str> {
+        match self {
+            DialogueNode::Text { text, .. } if index == 0 => Some(text),
+            DialogueNode::Text { variants, .. } => variants.get(index - 1).map(String::as_str),
+            _ => None,
+        }
+    }
+    
+    /// Gets the timeout of a Choice node in seconds, if it has one.
+    pub fn choice_timeout(&self) -> Option<f32> {
+        match self {
+            DialogueNode::Choice { timeout, .. } => *timeout,
+            _ => None,
+        }
+    }
+    
+    /// Gets the text shown by this node: the text of a Text node or the prompt of a
+    /// Choice or Input node.
+    pub fn display_text(&self) -> Option<&str> {
+        match self {
+            DialogueNode::Text { text, .. } => Some(text),
+            DialogueNode::Choice { prompt, .. } | DialogueNode::Input { prompt, .. } => {
+                prompt.as_deref()
+            }
+            DialogueNode::Random { .. }
+            | DialogueNode::Wait { .. }
+            | DialogueNode::Return { .. }
+            | DialogueNode::End { .. }
+            | DialogueNode::Custom { .. } => None,
+        }
+    }
 }
 
 impl DialogueElement for DialogueNode {
@@ -309,6 +871,12 @@ impl DialogueElement for DialogueNode {
         match self {
             DialogueNode::Text { id, .. } => *id,
             DialogueNode::Choice { id, .. } => *id,
+            DialogueNode::Random { id, .. } => *id,
+            DialogueNode::Wait { id, .. } => *id,
+            DialogueNode::Input { id, .. } => *id,
+            DialogueNode::Return { id } => *id,
+            DialogueNode::End { id, .. } => *id,
+            DialogueNode::Custom { id, .. } => *id,
         }
     }
 
@@ -332,6 +900,16 @@ impl DialogueElement for DialogueNode {
                     "Choice".to_string()
                 }
             },
+            DialogueNode::Random { mode, .. } => format!("[Random: {:?}]", mode),
+            DialogueNode::Wait { duration, .. } => format!("[Wait: {}s]", duration),
+            DialogueNode::Input { variable, .. } => format!("[Input: {}]", variable),
+            DialogueNode::Return { .. } => "[Return to hub]".to_string(),
+            DialogueNode::End {
+                outcome: Some(outcome),
+                ..
+            } => format!("[End: {}]", outcome),
+            DialogueNode::End { outcome: None, .. } => "[End]".to_string(),
+            DialogueNode::Custom { node_type, .. } => format!("[{}]", node_type),
         }
     }
 }
\ No newline at end of file
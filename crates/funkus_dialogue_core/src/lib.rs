@@ -12,10 +12,10 @@
 //!
 //! - **Asset System**: Define dialogues in JSON format with a flexible node-based structure
 //! - **Runtime Engine**: Process dialogues during gameplay, handling player choices and state transitions
-//! - **Node Types**: Support for text, choice, and other specialized node types
-//! - **Event System**: *Coming soon* - Type-safe events for integrating dialogues with game systems
+//! - **Node Types**: Text, choice, random, wait, input, return and end nodes, plus game-specific custom node types
+//! - **Event System**: Type-safe events and entity triggers for integrating dialogues with game systems
 //! - **Debug Tools**: Built-in debugging utilities for dialogue development
-//! - **Editor**: *Coming soon* - A visual editor for creating and editing dialogues
+//! - **Editor**: A visual in-game editor for creating and editing dialogues (`editor` feature)
 //!
 //! ## Basic Usage
 //!
@@ -48,6 +48,7 @@
 //!     start_events.send(funkus_dialogue::StartDialogue {
 //!         entity,
 //!         dialogue_handle,
+//!         entry_point: None,
 //!     });
 //! }
 //! ```
@@ -86,23 +87,36 @@ mod runtime;
 // Conditionally include the debug module
 #[cfg(feature = "debug_ui")]
 mod debug;
+#[cfg(feature = "editor")]
+mod editor;
 
 // Re-exports for public API
-pub use asset::DialogueAsset;
+pub use asset::{CharacterDatabase, CharacterDefinition, DialogueAsset, DialogueCharacters};
 #[cfg(feature = "debug_ui")]
 pub use debug::DialogueDebugPlugin;
+#[cfg(feature = "editor")]
+pub use editor::{DialogueEditorPlugin, DialogueEditorState};
+pub use error::{DialogueAction, DialogueError, DialogueResult, GraphError, UnknownSpeakersError};
 pub use events::{
-    AdvanceDialogue, DialogueChoiceMade, DialogueEnded, DialogueNodeActivated, DialogueStarted,
-    SelectDialogueChoice, StartDialogue, StopDialogue,
+    AdvanceDialogue, DialogueChoiceInfo, DialogueChoiceMade, DialogueChoiceTimedOut, DialogueEnded,
+    DialogueErrorOccurred, DialogueNodeActivated, DialogueNotifications, DialogueReloadWarning,
+    DialogueStartFailed, DialogueStarted, JumpDialogue, SelectDialogueChoice, StartDialogue,
+    StopDialogue, SubmitDialogueInput,
+};
+pub use graph::{
+    AdvanceTiming, Connection, CustomDialogueNode, CustomNodeAdvance, DialogueGraph, DialogueNode,
+    DialogueNodeAppExt, DialogueNodeKind, DialogueNodeRegistry, EntryPoint, InputRule, NodeId,
+    NodeMetadata, RandomMode, TextVariantMode,
+};
+pub use runtime::{
+    DialogueErrorPolicy, DialogueRunner, DialogueState, DialogueSystemSet, PendingDialogueStarts,
 };
-pub use graph::{Connection, DialogueGraph, DialogueNode, NodeId};
-pub use runtime::{DialogueRunner, DialogueState};
 
 /// Plugin that sets up the dialogue system components, assets, and systems.
 ///
 /// This plugin handles the registration of:
 ///
-/// - Custom assets (DialogueAsset)
+/// - Custom assets (DialogueAsset, CharacterDatabase)
 /// - Events for dialogue interaction
 /// - Systems for processing dialogues
 /// - Runtime components
@@ -130,15 +144,24 @@ impl Plugin for DialoguePlugin {
             .register_type::<runtime::DialogueState>()
             .add_plugins(bevy_common_assets::json::JsonAssetPlugin::<
                 asset::DialogueAsset,
-            >::new(&["dialogue.json"]));
+            >::new(&["dialogue.json"]))
+            .add_plugins(bevy_common_assets::json::JsonAssetPlugin::<
+                asset::CharacterDatabase,
+            >::new(&["characters.json"]));
 
         // Register events
         app.add_event::<events::DialogueStarted>()
             .add_event::<events::DialogueEnded>()
             .add_event::<events::DialogueNodeActivated>()
             .add_event::<events::DialogueChoiceMade>()
+            .add_event::<events::DialogueChoiceTimedOut>()
+            .add_event::<events::DialogueReloadWarning>()
+            .add_event::<events::DialogueStartFailed>()
+            .add_event::<events::DialogueErrorOccurred>()
             .add_event::<events::AdvanceDialogue>()
             .add_event::<events::SelectDialogueChoice>()
+            .add_event::<events::SubmitDialogueInput>()
+            .add_event::<events::JumpDialogue>()
             .add_event::<events::StartDialogue>()
             .add_event::<events::StopDialogue>();
 
@@ -4,11 +4,19 @@
 //! and the DialogueState enum, which represents the current state of a dialogue.
 
 use bevy::prelude::*;
-use std::collections::HashMap;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::collections::{HashMap, HashSet};
 
 use crate::asset::DialogueAsset;
-use crate::error::{DialogueError, DialogueResult};
-use crate::graph::{DialogueNode, NodeId};
+use crate::error::{DialogueAction, DialogueError, DialogueResult};
+use crate::graph::{
+    AdvanceTiming, ConnectionData, DialogueNode, NodeId, RandomMode, TextVariantMode,
+};
+
+/// Maximum number of nodes a runner passes through before giving up.
+const MAX_PASS_THROUGH_HOPS: usize = 64;
 
 /// Current state of a dialogue.
 ///
@@ -22,6 +30,8 @@ use crate::graph::{DialogueNode, NodeId};
 /// * `ShowingText` - Dialogue is displaying text
 /// * `WaitingForChoice` - Dialogue is waiting for player to select a choice
 /// * `ChoiceSelected(usize)` - Player has selected a choice, ready to advance
+/// * `Waiting` - Dialogue is on a wait or custom node, waiting to be advanced
+/// * `WaitingForInput` - Dialogue is waiting for the player to submit an input
 /// * `Finished` - Dialogue has reached an end node
 /// * `Error(String)` - Dialogue encountered an error
 ///
@@ -29,11 +39,12 @@ use crate::graph::{DialogueNode, NodeId};
 ///
 /// The possible state transitions are:
 ///
-/// - `Inactive` -> `ShowingText` or `WaitingForChoice` (when starting)
-/// - `ShowingText` -> `ShowingText` or `WaitingForChoice` or `Finished` (when advancing)
+/// - `Inactive` -> any node state (when starting)
+/// - `ShowingText` or `Waiting` -> any node state or `Finished` (when advancing)
+/// - `WaitingForInput` -> any node state or `Finished` (when submitting a valid input)
 /// - `WaitingForChoice` -> `ChoiceSelected` (when selecting)
 /// - `ChoiceSelected` -> `ChoiceSelected` (when selecting a different choice)
-/// - `ChoiceSelected` -> `ShowingText` or `WaitingForChoice` or `Finished` (when advancing)
+/// - `ChoiceSelected` -> any node state or `Finished` (when advancing)
 /// - Any state -> `Inactive` (when stopping)
 /// - Any state -> `Error` (when an error occurs)
 #[derive(Debug, Clone, Reflect, PartialEq, Eq)]
@@ -46,6 +57,10 @@ pub enum DialogueState {
     WaitingForChoice,
     /// Player has selected a choice, ready to advance to next node
     ChoiceSelected(usize),
+    /// Dialogue is on a wait or custom node, waiting to be advanced
+    Waiting,
+    /// Dialogue is waiting for the player to submit an input
+    WaitingForInput,
     /// Dialogue has reached an end node
     Finished,
     /// Dialogue encountered an error
@@ -64,6 +79,8 @@ impl DialogueState {
             DialogueState::ShowingText => "ShowingText".to_string(),
             DialogueState::WaitingForChoice => "WaitingForChoice".to_string(),
             DialogueState::ChoiceSelected(_) => "ChoiceSelected".to_string(),
+            DialogueState::Waiting => "Waiting".to_string(),
+            DialogueState::WaitingForInput => "WaitingForInput".to_string(),
             DialogueState::Finished => "Finished".to_string(),
             DialogueState::Error(_) => "Error".to_string(),
         }
@@ -77,7 +94,7 @@ impl DialogueState {
     pub fn can_advance(&self) -> bool {
         matches!(
             self,
-            DialogueState::ShowingText | DialogueState::ChoiceSelected(_)
+            DialogueState::ShowingText | DialogueState::ChoiceSelected(_) | DialogueState::Waiting
         )
     }
 
@@ -94,6 +111,25 @@ impl DialogueState {
     }
 }
 
+/// What a dialogue runner does when an action on its dialogue fails.
+///
+/// Errors are always reported with a
+/// [`DialogueErrorOccurred`](crate::events::DialogueErrorOccurred) event; the policy
+/// decides how the dialogue continues afterwards.
+#[derive(Debug, Clone, Copy, Default, Reflect, PartialEq, Eq)]
+pub enum DialogueErrorPolicy {
+    /// Stop the dialogue, as if a `StopDialogue` event was sent
+    Stop,
+    /// Stay on the current node, so the action can be retried.
+    ///
+    /// Failed automatic advances, such as auto-advance or choice timeouts, aren't
+    /// retried until the runner enters another node.
+    #[default]
+    Stay,
+    /// Skip to the next node that can be entered, finishing the dialogue if there is none
+    Skip,
+}
+
 /// Component that processes and manages a dialogue.
 ///
 /// DialogueRunner is the core component for dialogue runtime processing.
@@ -108,8 +144,19 @@ impl DialogueState {
 /// * `state` - Current state of the dialogue
 /// * `auto_advance` - Whether the dialogue should auto-advance after text nodes
 /// * `auto_advance_time` - Time to wait for auto-advance (in seconds)
-/// * `auto_advance_timer` - Timer for auto-advance
+/// * `auto_advance_timer` - Timer for auto-advance and choice timeouts
 /// * `variables` - Simple variable storage
+/// * `history` - Nodes visited since the dialogue started
+/// * `error_policy` - What to do when an action on the dialogue fails
+/// * `rng` - Random number generator used by random nodes
+/// * `random_picks` - Prior picks of shuffle and cycle random nodes
+/// * `line_counts` - Number of lines shown by each text node
+/// * `shuffled_lines` - Lines left to show by shuffled text nodes
+/// * `current_line` - Line of the current text node being shown
+/// * `taken_choices` - Once-only choices that were taken
+/// * `hubs` - Hub choice nodes entered since the dialogue started
+/// * `outcome` - Outcome of the end node that finished the dialogue
+/// * `entry_point` - Entry point the dialogue started from
 ///
 /// # Auto-Advance Feature
 ///
@@ -122,8 +169,38 @@ impl DialogueState {
 /// - `auto_advance_timer`: Internal timer that tracks elapsed time
 ///
 /// Auto-advance only applies to text nodes and is ignored for choice nodes.
+/// Text nodes can override it with their own [`AdvanceTiming`], and wait nodes
+/// always move on after their duration; see [`advance_delay`](Self::advance_delay).
 /// The timer is reset whenever the dialogue advances to a new node.
 ///
+/// # Timed Choices
+///
+/// Choice nodes with a timeout take their default choice when the player doesn't
+/// choose in time. The countdown shares the auto-advance timer; see
+/// [`choice_time_left`](Self::choice_time_left).
+///
+/// # Random Nodes
+///
+/// Random nodes use the runner's own random number generator. It is seeded from
+/// entropy by default; use [`with_seed`](Self::with_seed) to make the picks
+/// deterministic for tests and replays. The picks of shuffle and cycle nodes are
+/// remembered across dialogues run by the same runner.
+///
+/// # Hubs and Once-Only Choices
+///
+/// Choices whose connection is marked `once` are removed from their choice node after
+/// they have been taken, see [`available_choices`](Self::available_choices). Taken
+/// choices are remembered across dialogues run by the same runner, until
+/// [`taken_choices`](Self::taken_choices) is cleared. Return nodes lead
+/// back to the last hub choice node entered that still has choices.
+///
+/// # Text Variants
+///
+/// Text nodes with variants show a different line each time they are entered, as
+/// decided by their [`TextVariantMode`]. The runner counts the lines shown by each
+/// node across dialogues, and [`current_text`](Self::current_text) gets the line
+/// being shown.
+///
 /// # Example
 ///
 /// ```rust
@@ -150,10 +227,35 @@ pub struct DialogueRunner {
     pub auto_advance: bool,
     /// Time to wait for auto-advance (in seconds)
     pub auto_advance_time: f32,
-    /// Timer for auto-advance
+    /// Timer for auto-advance and choice timeouts
     pub auto_advance_timer: Timer,
     /// Simple variable storage (to be expanded later)
     pub variables: HashMap<String, String>,
+    /// Nodes visited since the dialogue started, in the order they were entered
+    pub history: Vec<NodeId>,
+    /// What to do when an action on the dialogue fails
+    pub error_policy: DialogueErrorPolicy,
+    /// Random number generator used by random nodes
+    pub rng: ChaCha8Rng,
+    /// Prior picks of shuffle and cycle random nodes, by node
+    pub random_picks: HashMap<NodeId, Vec<NodeId>>,
+    /// Number of lines shown by each text node
+    pub line_counts: HashMap<NodeId, usize>,
+    /// Lines left to show in the current round of shuffled text nodes, by node
+    pub shuffled_lines: HashMap<NodeId, Vec<usize>>,
+    /// Line of the current text node being shown, 0 being the node text
+    pub current_line: usize,
+    /// Once-only choices that were taken, as (choice node, target node) pairs.
+    ///
+    /// Unlike the history, this is kept when a dialogue is restarted, so options taken
+    /// in an earlier conversation stay gone. Clear it to offer them again.
+    pub taken_choices: HashSet<(NodeId, NodeId)>,
+    /// Hub choice nodes entered since the dialogue started, the most recent last
+    pub hubs: Vec<NodeId>,
+    /// Outcome of the end node that finished the dialogue, if any
+    pub outcome: Option<String>,
+    /// Name of the entry point the dialogue started from, or None for the start node
+    pub entry_point: Option<String>,
 }
 
 impl Default for DialogueRunner {
@@ -166,6 +268,17 @@ impl Default for DialogueRunner {
             auto_advance_time: 2.0,
             auto_advance_timer: Timer::from_seconds(2.0, TimerMode::Once),
             variables: HashMap::new(),
+            history: Vec::new(),
+            error_policy: DialogueErrorPolicy::default(),
+            rng: ChaCha8Rng::from_entropy(),
+            random_picks: HashMap::new(),
+            line_counts: HashMap::new(),
+            shuffled_lines: HashMap::new(),
+            current_line: 0,
+            taken_choices: HashSet::new(),
+            hubs: Vec::new(),
+            outcome: None,
+            entry_point: None,
         }
     }
 }
@@ -200,20 +313,44 @@ impl DialogueRunner {
         }
     }
 
+    /// Builder method to seed the random number generator used by random nodes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use funkus_dialogue_core::DialogueRunner;
+    ///
+    /// // Two runners with the same seed make the same random picks
+    /// let runner = DialogueRunner::default().with_seed(42);
+    /// ```
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = ChaCha8Rng::seed_from_u64(seed);
+        self
+    }
+
     /// Starts the dialogue from the beginning.
     ///
     /// This method initializes the dialogue runner with the start node
     /// from the provided dialogue asset and sets the appropriate initial state.
     ///
+    /// If the dialogue has entry points with a condition, the first one whose
+    /// condition holds on the runner variables is started from instead, see
+    /// [`EntryPoint`](crate::graph::EntryPoint).
+    ///
     /// # Parameters
     ///
     /// * `dialogue` - The dialogue asset to start
     ///
+    /// # Errors
+    ///
+    /// Returns `NodeNotFound` if the start node doesn't exist, or the errors of entering
+    /// the nodes it passes through to, in which case the runner is left untouched
+    ///
     /// # Example
     ///
     /// ```rust
     /// # use bevy::prelude::*;
-    /// # use funkus_dialogue::{DialogueRunner, DialogueAsset};
+    /// # use funkus_dialogue_core::{DialogueRunner, DialogueAsset};
     /// #
     /// fn start_dialogue(
     ///     dialogue_assets: Res<Assets<DialogueAsset>>,
@@ -221,27 +358,131 @@ impl DialogueRunner {
     /// ) {
     ///     for mut runner in dialogue_query.iter_mut() {
     ///         if let Some(dialogue) = dialogue_assets.get(&runner.dialogue_handle) {
-    ///             runner.start(dialogue);
+    ///             if let Err(err) = runner.start(dialogue) {
+    ///                 eprintln!("Error starting dialogue: {}", err);
+    ///             }
     ///         }
     ///     }
     /// }
     /// ```
-    pub fn start(&mut self, dialogue: &DialogueAsset) {
-        let start_id = dialogue.graph.start_node;
-        self.current_node_id = Some(start_id);
+    pub fn start(&mut self, dialogue: &DialogueAsset) -> DialogueResult<()> {
+        let entry_point = dialogue.graph.entry_points.iter().find(|entry_point| {
+            entry_point
+                .condition
+                .as_deref()
+                .is_some_and(|condition| self.check_condition(condition))
+        });
 
-        // Set initial state based on the start node type
-        if let Some(node) = dialogue.graph.get_node(start_id) {
-            match node {
-                DialogueNode::Text { .. } => self.state = DialogueState::ShowingText,
-                DialogueNode::Choice { .. } => self.state = DialogueState::WaitingForChoice,
-            }
-        } else {
-            self.state = DialogueState::Error(format!("Start node {:?} not found", start_id));
+        match entry_point {
+            Some(entry_point) => self.begin(
+                dialogue,
+                entry_point.node_id,
+                Some(entry_point.name.clone()),
+            ),
+            None => self.begin(dialogue, dialogue.graph.start_node, None),
+        }
+    }
+
+    /// Starts the dialogue from the entry point with the given name.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UnknownEntryPoint` if the dialogue has no such entry point, or the
+    /// errors of [`start`](Self::start), in which case the runner is left untouched
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use funkus_dialogue_core::graph::{DialogueGraph, DialogueNode, EntryPoint, NodeId};
+    /// use funkus_dialogue_core::{DialogueAsset, DialogueRunner};
+    ///
+    /// let graph = DialogueGraph::new(NodeId(1))
+    ///     .with_node(DialogueNode::text(NodeId(1), "Hello, stranger."))
+    ///     .with_node(DialogueNode::text(NodeId(2), "Thanks again for your help!"))
+    ///     .with_entry_point(EntryPoint::new("quest_done", NodeId(2)));
+    /// let dialogue = DialogueAsset::new(graph);
+    ///
+    /// let mut runner = DialogueRunner::default();
+    /// runner.start_at(&dialogue, "quest_done").unwrap();
+    /// assert_eq!(runner.current_node_id, Some(NodeId(2)));
+    /// ```
+    pub fn start_at(&mut self, dialogue: &DialogueAsset, entry_point: &str) -> DialogueResult<()> {
+        let node_id = dialogue
+            .graph
+            .entry_point(entry_point)
+            .ok_or_else(|| DialogueError::UnknownEntryPoint(entry_point.to_string()))?
+            .node_id;
+        self.begin(dialogue, node_id, Some(entry_point.to_string()))
+    }
+
+    /// Moves the dialogue to the given node.
+    ///
+    /// The state is set according to the node type, just like when the node is reached
+    /// by advancing. The dialogue keeps its history, so this can redirect a conversation
+    /// mid-flow. If the dialogue isn't running, it is started at the node instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NodeNotFound` if the node doesn't exist in the dialogue, in which case
+    /// the runner is left untouched
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use funkus_dialogue_core::graph::{DialogueGraph, DialogueNode, NodeId};
+    /// use funkus_dialogue_core::{DialogueAsset, DialogueRunner, DialogueState};
+    ///
+    /// let graph = DialogueGraph::new(NodeId(1))
+    ///     .with_node(DialogueNode::text(NodeId(1), "Nice weather today."))
+    ///     .with_node(DialogueNode::text(NodeId(2), "Ouch! What was that for?"));
+    /// let dialogue = DialogueAsset::new(graph);
+    ///
+    /// let mut runner = DialogueRunner::default();
+    /// runner.start(&dialogue).unwrap();
+    /// runner.jump_to(&dialogue, NodeId(2)).unwrap();
+    /// assert_eq!(runner.current_node_id, Some(NodeId(2)));
+    /// assert_eq!(runner.state, DialogueState::ShowingText);
+    /// ```
+    pub fn jump_to(&mut self, dialogue: &DialogueAsset, node_id: NodeId) -> DialogueResult<()> {
+        if !dialogue.graph.contains_node(node_id) {
+            return Err(DialogueError::NodeNotFound(node_id));
+        }
+        if !self.is_running() {
+            self.reset_progress(None);
         }
+        self.enter_node(dialogue, node_id)
+    }
+
+    /// Starts the dialogue from the given node, leaving the runner untouched on error.
+    fn begin(
+        &mut self,
+        dialogue: &DialogueAsset,
+        start_id: NodeId,
+        entry_point: Option<String>,
+    ) -> DialogueResult<()> {
+        let progress = (
+            self.entry_point.clone(),
+            std::mem::take(&mut self.history),
+            std::mem::take(&mut self.hubs),
+            self.outcome.clone(),
+        );
+        self.reset_progress(entry_point);
+
+        // Set initial state based on the start node type
+        self.enter_node(dialogue, start_id).inspect_err(|_| {
+            (self.entry_point, self.history, self.hubs, self.outcome) = progress;
+        })
+    }
 
-        // Reset timer for auto-advance
-        self.auto_advance_timer.reset();
+    /// Forgets the progress of the previous dialogue.
+    ///
+    /// What is remembered across dialogues, such as taken once-only choices and the
+    /// lines shown by text nodes, is kept.
+    fn reset_progress(&mut self, entry_point: Option<String>) {
+        self.entry_point = entry_point;
+        self.history.clear();
+        self.hubs.clear();
+        self.outcome = None;
     }
 
     /// Advances to the next node in the dialogue.
@@ -266,6 +507,10 @@ impl DialogueRunner {
     /// - `NodeNotFound` - The current node ID doesn't exist in the dialogue
     /// - `NoChoiceSelected` - Trying to advance from a choice node without a selection
     /// - `NextNodeNotFound` - The target node doesn't exist
+    /// - `NodeNotFound` - A node the target passes through to doesn't exist
+    /// - `PassThroughLoop` - Nodes passed through keep leading to each other
+    ///
+    /// The runner is left on the current node when advancing fails.
     ///
     /// # Example
     ///
@@ -292,8 +537,8 @@ impl DialogueRunner {
         // Check if we can advance in the current state
         if !self.state.can_advance() {
             return Err(DialogueError::InvalidStateTransition {
-                from: self.state.name(),
-                action: "advance".to_string(),
+                from: self.state.clone(),
+                action: DialogueAction::Advance,
             });
         }
 
@@ -305,11 +550,11 @@ impl DialogueRunner {
             .ok_or(DialogueError::NodeNotFound(current_id))?;
 
         match current_node {
-            DialogueNode::Text { .. } => {
+            DialogueNode::Text { .. } | DialogueNode::Wait { .. } | DialogueNode::Custom { .. } => {
                 // Get connections from the graph instead of the node
                 let connections = dialogue.graph.get_connected_nodes(current_id);
 
-                // A text node typically has 0 or 1 connections
+                // A text, wait or custom node typically has 0 or 1 connections
                 if connections.is_empty() {
                     // End of dialogue
                     self.state = DialogueState::Finished;
@@ -317,18 +562,7 @@ impl DialogueRunner {
                 }
 
                 // Move to the next node
-                let next_id = connections[0].0;
-                self.current_node_id = Some(next_id);
-
-                // Update state based on the next node type
-                if let Some(next_node) = dialogue.graph.get_node(next_id) {
-                    match next_node {
-                        DialogueNode::Text { .. } => self.state = DialogueState::ShowingText,
-                        DialogueNode::Choice { .. } => self.state = DialogueState::WaitingForChoice,
-                    }
-                } else {
-                    return Err(DialogueError::NextNodeNotFound(next_id));
-                }
+                self.enter_next(dialogue, connections[0].0)?;
             }
             DialogueNode::Choice { .. } => {
                 // For choice nodes, we need a selected choice
@@ -340,38 +574,578 @@ impl DialogueRunner {
                     }
                 };
 
-                // Get connections from the graph
-                let connections = dialogue.graph.get_connected_nodes(current_id);
+                // Taken once-only choices aren't offered anymore
+                let choices = self.available_choices(dialogue, current_id);
 
-                if choice_index >= connections.len() {
+                if choice_index >= choices.len() {
                     return Err(DialogueError::InvalidChoiceIndex(
                         choice_index,
-                        connections.len() - 1,
+                        choices.len().saturating_sub(1),
                     ));
                 }
 
                 // Move to the selected choice's target node
-                let next_id = connections[choice_index].0;
-                self.current_node_id = Some(next_id);
-
-                // Update state based on the next node type
-                if let Some(next_node) = dialogue.graph.get_node(next_id) {
-                    match next_node {
-                        DialogueNode::Text { .. } => self.state = DialogueState::ShowingText,
-                        DialogueNode::Choice { .. } => self.state = DialogueState::WaitingForChoice,
+                let (next_id, connection) = choices[choice_index];
+                if connection.once {
+                    self.taken_choices.insert((current_id, next_id));
+                }
+                if let Err(err) = self.enter_next(dialogue, next_id) {
+                    // The choice wasn't taken after all
+                    if connection.once {
+                        self.taken_choices.remove(&(current_id, next_id));
                     }
-                } else {
-                    return Err(DialogueError::NextNodeNotFound(next_id));
+                    return Err(err);
                 }
             }
+            DialogueNode::Random { .. } | DialogueNode::Return { .. } => {
+                // Runners don't stop on random and return nodes, enter it again
+                self.enter_node(dialogue, current_id)?;
+            }
+            DialogueNode::End { .. } => {
+                // End nodes finish the dialogue as soon as they are entered
+                self.state = DialogueState::Finished;
+            }
+            DialogueNode::Input { .. } => {
+                // Input nodes are left by submitting an input
+                return Err(DialogueError::InvalidStateTransition {
+                    from: self.state.clone(),
+                    action: DialogueAction::Advance,
+                });
+            }
         }
 
-        // Reset timer for auto-advance
-        self.auto_advance_timer.reset();
-
         Ok(())
     }
 
+    /// Remaps the runner onto a reloaded version of its dialogue.
+    ///
+    /// The runner stays on its current node if it still exists. Its state is adjusted
+    /// if the node changed type, and a selected choice that no longer exists is
+    /// deselected. If the current node was removed, the runner falls back to the
+    /// start node, or is left in the `Error` state if the dialogue can't be started
+    /// either. Inactive and finished runners are left untouched.
+    ///
+    /// # Parameters
+    ///
+    /// * `dialogue` - The reloaded dialogue asset
+    ///
+    /// # Returns
+    ///
+    /// The ID of the missing node if the runner had to fall back, None otherwise
+    pub fn reload(&mut self, dialogue: &DialogueAsset) -> Option<NodeId> {
+        if matches!(
+            self.state,
+            DialogueState::Inactive | DialogueState::Finished
+        ) {
+            return None;
+        }
+
+        let current_id = self.current_node_id?;
+        let Some(node) = dialogue.graph.get_node(current_id) else {
+            self.restart(dialogue);
+            return Some(current_id);
+        };
+
+        // A node that became a random, return or end node is entered again
+        if matches!(
+            node,
+            DialogueNode::Random { .. } | DialogueNode::Return { .. } | DialogueNode::End { .. }
+        ) {
+            if self.enter_node(dialogue, current_id).is_err() {
+                self.restart(dialogue);
+            }
+            return None;
+        }
+
+        self.state = match (node, &self.state) {
+            (DialogueNode::Text { .. }, _) => DialogueState::ShowingText,
+            (DialogueNode::Choice { .. }, DialogueState::ChoiceSelected(index))
+                if *index < self.available_choices(dialogue, current_id).len() =>
+            {
+                DialogueState::ChoiceSelected(*index)
+            }
+            (DialogueNode::Choice { .. }, _) => DialogueState::WaitingForChoice,
+            (DialogueNode::Wait { .. } | DialogueNode::Custom { .. }, _) => DialogueState::Waiting,
+            (DialogueNode::Input { .. }, _) => DialogueState::WaitingForInput,
+            (
+                DialogueNode::Random { .. }
+                | DialogueNode::Return { .. }
+                | DialogueNode::End { .. },
+                _,
+            ) => unreachable!("random, return and end nodes are entered above"),
+        };
+
+        None
+    }
+
+    /// Starts the dialogue again after a reload, going to the `Error` state on failure.
+    fn restart(&mut self, dialogue: &DialogueAsset) {
+        if let Err(err) = self.start(dialogue) {
+            self.state = DialogueState::Error(err.to_string());
+        }
+    }
+
+    /// Skips past the current node, as done by [`DialogueErrorPolicy::Skip`].
+    ///
+    /// The runner enters the first connected node that exists. If there is none,
+    /// the dialogue is finished.
+    ///
+    /// # Returns
+    ///
+    /// The ID of the node that was entered, or None if the dialogue finished
+    pub fn skip(&mut self, dialogue: &DialogueAsset) -> Option<NodeId> {
+        let next_id = self.current_node_id.and_then(|id| {
+            dialogue
+                .graph
+                .get_connections(id)
+                .into_iter()
+                .map(|(target_id, _)| target_id)
+                .find(|target_id| dialogue.graph.contains_node(*target_id))
+        });
+
+        match next_id {
+            Some(next_id) if self.enter_node(dialogue, next_id).is_ok() => Some(next_id),
+            _ => {
+                self.state = DialogueState::Finished;
+                None
+            }
+        }
+    }
+
+    /// Makes the given node the current node.
+    ///
+    /// The state is set according to the node type, the node is appended to the history
+    /// and the auto-advance timer is reset. Text nodes pick the line they show.
+    ///
+    /// Random nodes, return nodes, choice nodes whose choices were all taken and
+    /// once-only text nodes that showed all their lines are passed through: the node
+    /// they lead to is entered in turn, and the dialogue finishes if there is none.
+    /// Entering a hub choice node makes it the node return nodes lead to, and entering
+    /// an end node finishes the dialogue with the node's outcome.
+    /// The runner is left untouched if entering fails, even part way through the
+    /// passed-through nodes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NodeNotFound` if the node, or a node it passes through to, doesn't
+    /// exist in the dialogue, or `PassThroughLoop` if passed-through nodes keep
+    /// leading to each other
+    pub(crate) fn enter_node(
+        &mut self,
+        dialogue: &DialogueAsset,
+        id: NodeId,
+    ) -> DialogueResult<()> {
+        let snapshot = EnterSnapshot::take(self);
+        let result = self.pass_through(dialogue, id);
+        if result.is_err() {
+            snapshot.restore(self);
+        }
+        result
+    }
+
+    /// Enters the given node and the nodes it passes through to, see
+    /// [`enter_node`](Self::enter_node).
+    fn pass_through(&mut self, dialogue: &DialogueAsset, id: NodeId) -> DialogueResult<()> {
+        let mut id = id;
+        for _ in 0..MAX_PASS_THROUGH_HOPS {
+            let node = dialogue
+                .graph
+                .get_node(id)
+                .ok_or(DialogueError::NodeNotFound(id))?;
+
+            self.current_node_id = Some(id);
+            self.history.push(id);
+
+            // Reset timer for auto-advance, resuming it if an error paused it
+            self.auto_advance_timer.reset();
+            self.auto_advance_timer.unpause();
+
+            self.state = match node {
+                DialogueNode::Text { variant_mode, .. } => {
+                    match self.pick_line(node, id, *variant_mode) {
+                        Some(line) => {
+                            self.current_line = line;
+                            DialogueState::ShowingText
+                        }
+                        // Once-only lines are used up, move on as if advanced
+                        None => match dialogue.graph.get_connected_nodes(id).first() {
+                            Some((next_id, _)) => {
+                                id = *next_id;
+                                continue;
+                            }
+                            None => DialogueState::Finished,
+                        },
+                    }
+                }
+                DialogueNode::Choice { hub, .. } => {
+                    if *hub {
+                        self.hubs.retain(|hub_id| *hub_id != id);
+                        self.hubs.push(id);
+                    }
+
+                    let exhausted = self.available_choices(dialogue, id).is_empty()
+                        && !dialogue.graph.get_connections(id).is_empty();
+                    if exhausted {
+                        // Every choice was taken, leave as if returning to the hub
+                        self.hubs.retain(|hub_id| *hub_id != id);
+                        match self.return_target(dialogue) {
+                            Some(hub_id) => {
+                                id = hub_id;
+                                continue;
+                            }
+                            None => DialogueState::Finished,
+                        }
+                    } else {
+                        DialogueState::WaitingForChoice
+                    }
+                }
+                DialogueNode::End { outcome, .. } => {
+                    self.outcome = outcome.clone();
+                    DialogueState::Finished
+                }
+                DialogueNode::Return { .. } => match self.return_target(dialogue) {
+                    Some(hub_id) => {
+                        id = hub_id;
+                        continue;
+                    }
+                    None => DialogueState::Finished,
+                },
+                DialogueNode::Wait { .. } | DialogueNode::Custom { .. } => DialogueState::Waiting,
+                DialogueNode::Input { .. } => DialogueState::WaitingForInput,
+                DialogueNode::Random { mode, .. } => match self.pick_random(dialogue, id, *mode) {
+                    Some(next_id) => {
+                        id = next_id;
+                        continue;
+                    }
+                    None => DialogueState::Finished,
+                },
+            };
+
+            return Ok(());
+        }
+
+        Err(DialogueError::PassThroughLoop(id))
+    }
+
+    /// Enters the node a connection leads to.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NextNodeNotFound` if the node doesn't exist in the dialogue, or the
+    /// errors of [`enter_node`](Self::enter_node) for the nodes it passes through to
+    fn enter_next(&mut self, dialogue: &DialogueAsset, next_id: NodeId) -> DialogueResult<()> {
+        if !dialogue.graph.contains_node(next_id) {
+            return Err(DialogueError::NextNodeNotFound(next_id));
+        }
+        self.enter_node(dialogue, next_id)
+    }
+
+    /// Finds the hub return nodes lead to, forgetting hubs without choices left.
+    fn return_target(&mut self, dialogue: &DialogueAsset) -> Option<NodeId> {
+        while let Some(&hub_id) = self.hubs.last() {
+            if !self.available_choices(dialogue, hub_id).is_empty() {
+                return Some(hub_id);
+            }
+            self.hubs.pop();
+        }
+        None
+    }
+
+    /// Picks the line a text node shows and counts it as shown.
+    ///
+    /// # Returns
+    ///
+    /// The index of the line, or None if a once-only node showed all its lines
+    fn pick_line(
+        &mut self,
+        node: &DialogueNode,
+        id: NodeId,
+        mode: TextVariantMode,
+    ) -> Option<usize> {
+        let line_count = match node {
+            DialogueNode::Text { variants, .. } => variants.len() + 1,
+            _ => return None,
+        };
+        let shown = self.line_counts.entry(id).or_default();
+
+        let line = match mode {
+            TextVariantMode::Sequence => (*shown).min(line_count - 1),
+            TextVariantMode::Cycle => *shown % line_count,
+            TextVariantMode::Once if *shown < line_count => *shown,
+            TextVariantMode::Once => return None,
+            TextVariantMode::Shuffle => {
+                let remaining = self.shuffled_lines.entry(id).or_default();
+                // Drop lines removed since the round started
+                remaining.retain(|line| *line < line_count);
+                if remaining.is_empty() {
+                    *remaining = (0..line_count).collect();
+                    remaining.shuffle(&mut self.rng);
+                }
+                remaining.pop().unwrap_or_default()
+            }
+        };
+
+        *shown += 1;
+        Some(line)
+    }
+
+    /// Gets how long the current node waits before the dialogue moves on by itself.
+    ///
+    /// Wait nodes wait for their duration. Text nodes follow their [`AdvanceTiming`],
+    /// which defaults to the `auto_advance` settings of the runner.
+    ///
+    /// # Returns
+    ///
+    /// The delay in seconds, or None if the dialogue only moves on when advanced
+    pub fn advance_delay(&self, dialogue: &DialogueAsset) -> Option<f32> {
+        if !matches!(
+            self.state,
+            DialogueState::ShowingText | DialogueState::Waiting
+        ) {
+            return None;
+        }
+
+        match self.current_node(dialogue)? {
+            DialogueNode::Text { advance, .. } => match advance {
+                AdvanceTiming::Runner => self.auto_advance.then_some(self.auto_advance_time),
+                AdvanceTiming::After(delay) => Some(*delay),
+                AdvanceTiming::WaitForSignal => None,
+            },
+            DialogueNode::Wait { duration, .. } => Some(*duration),
+            _ => None,
+        }
+    }
+
+    /// Gets the timeout of the current node, if it is a timed choice waiting for a choice.
+    pub fn choice_timeout(&self, dialogue: &DialogueAsset) -> Option<f32> {
+        if !self.state.can_select_choice() {
+            return None;
+        }
+        self.current_node(dialogue)?.choice_timeout()
+    }
+
+    /// Gets the seconds left to choose before a timed choice takes its default choice.
+    ///
+    /// # Returns
+    ///
+    /// The seconds left, or None if the runner isn't waiting on a timed choice
+    pub fn choice_time_left(&self, dialogue: &DialogueAsset) -> Option<f32> {
+        let timeout = self.choice_timeout(dialogue)?;
+        Some((timeout - self.auto_advance_timer.elapsed_secs()).max(0.0))
+    }
+
+    /// Moves on from a choice node that ran out of time.
+    ///
+    /// The selected choice is taken if there is one, otherwise the default choice
+    /// of the node.
+    ///
+    /// # Returns
+    ///
+    /// The index of the choice taken
+    ///
+    /// # Errors
+    ///
+    /// Returns `NoChoices` if the node has no choice to take, `InvalidStateTransition`
+    /// if the runner isn't waiting for a choice, or the errors of [`advance`](Self::advance)
+    pub fn time_out_choice(&mut self, dialogue: &DialogueAsset) -> DialogueResult<usize> {
+        let choice_index = match self.state {
+            DialogueState::ChoiceSelected(index) => index,
+            _ => {
+                let index = match self.current_node(dialogue) {
+                    Some(DialogueNode::Choice {
+                        id, default_choice, ..
+                    }) => {
+                        // Taken once-only choices can leave the default out of range
+                        let choices = self.available_choices(dialogue, *id).len();
+                        if choices == 0 {
+                            return Err(DialogueError::NoChoices(*id));
+                        }
+                        (*default_choice).min(choices - 1)
+                    }
+                    _ => 0,
+                };
+                self.select_choice(dialogue, index)?;
+                index
+            }
+        };
+
+        self.advance(dialogue)?;
+        Ok(choice_index)
+    }
+
+    /// Gets the text shown by the current node.
+    ///
+    /// This is the line picked by a text node, or the prompt of a choice node.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use funkus_dialogue_core::graph::{DialogueGraph, DialogueNode, NodeId, TextVariantMode};
+    /// use funkus_dialogue_core::{DialogueAsset, DialogueRunner};
+    ///
+    /// let node = DialogueNode::text(NodeId(1), "Hello, stranger.")
+    ///     .with_variants(TextVariantMode::Sequence, ["Back again?"])
+    ///     .unwrap();
+    /// let dialogue = DialogueAsset::new(DialogueGraph::new(NodeId(1)).with_node(node));
+    ///
+    /// let mut runner = DialogueRunner::default();
+    /// runner.start(&dialogue).unwrap();
+    /// assert_eq!(runner.current_text(&dialogue), Some("Hello, stranger."));
+    /// runner.start(&dialogue).unwrap();
+    /// assert_eq!(runner.current_text(&dialogue), Some("Back again?"));
+    /// ```
+    pub fn current_text<'a>(&self, dialogue: &'a DialogueAsset) -> Option<&'a str> {
+        let node = dialogue.graph.get_node(self.current_node_id?)?;
+        node.line(self.current_line).or_else(|| node.display_text())
+    }
+
+    /// Picks the connection a random node follows.
+    ///
+    /// Only connections to existing nodes, with a positive weight and whose condition
+    /// holds are considered.
+    ///
+    /// # Returns
+    ///
+    /// The ID of the picked node, or None if no connection can be picked
+    fn pick_random(
+        &mut self,
+        dialogue: &DialogueAsset,
+        id: NodeId,
+        mode: RandomMode,
+    ) -> Option<NodeId> {
+        let candidates: Vec<(NodeId, f32)> = dialogue
+            .graph
+            .get_connections(id)
+            .into_iter()
+            .filter(|(target_id, data)| {
+                data.weight > 0.0
+                    && dialogue.graph.contains_node(*target_id)
+                    && data
+                        .condition
+                        .as_deref()
+                        .is_none_or(|condition| self.check_condition(condition))
+            })
+            .map(|(target_id, data)| (target_id, data.weight))
+            .collect();
+        if candidates.is_empty() {
+            return None;
+        }
+
+        let picks = self.random_picks.entry(id).or_default();
+        let pick = match mode {
+            RandomMode::Weighted => weighted_pick(&mut self.rng, &candidates),
+            RandomMode::Shuffle => {
+                let mut remaining: Vec<_> = candidates
+                    .iter()
+                    .filter(|(target_id, _)| !picks.contains(target_id))
+                    .copied()
+                    .collect();
+                if remaining.is_empty() {
+                    // Every connection was picked, start a new round
+                    picks.clear();
+                    remaining = candidates;
+                }
+                let pick = weighted_pick(&mut self.rng, &remaining);
+                picks.push(pick);
+                pick
+            }
+            RandomMode::Cycle => {
+                let next = picks
+                    .last()
+                    .and_then(|last| {
+                        candidates
+                            .iter()
+                            .position(|(target_id, _)| target_id == last)
+                    })
+                    .map_or(0, |index| (index + 1) % candidates.len());
+                let pick = candidates[next].0;
+                *picks = vec![pick];
+                pick
+            }
+        };
+
+        Some(pick)
+    }
+
+    /// Checks a condition on the runner variables.
+    ///
+    /// Supported conditions are:
+    ///
+    /// - `name` - the variable is set and isn't empty, `false` or `0`
+    /// - `!name` - the opposite
+    /// - `name == value` and `name != value` - compares the variable with a value
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use funkus_dialogue_core::DialogueRunner;
+    ///
+    /// let mut runner = DialogueRunner::default();
+    /// runner.variables.insert("weather".to_string(), "rain".to_string());
+    /// assert!(runner.check_condition("weather == rain"));
+    /// assert!(!runner.check_condition("met_guide"));
+    /// ```
+    pub fn check_condition(&self, condition: &str) -> bool {
+        let value_of = |name: &str| self.variables.get(name.trim()).map(String::as_str);
+        let condition = condition.trim();
+
+        if let Some((name, value)) = condition.split_once("!=") {
+            return value_of(name) != Some(value.trim());
+        }
+        if let Some((name, value)) = condition.split_once("==") {
+            return value_of(name) == Some(value.trim());
+        }
+
+        let (name, negated) = match condition.strip_prefix('!') {
+            Some(name) => (name, true),
+            None => (condition, false),
+        };
+        let is_set = value_of(name).is_some_and(|value| !matches!(value, "" | "false" | "0"));
+        is_set != negated
+    }
+
+    /// Gets the choices a choice node offers to this runner.
+    ///
+    /// These are the node's connections in order, minus the once-only connections
+    /// that were already taken. Choice indices, as used by
+    /// [`select_choice`](Self::select_choice), refer to this list.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use funkus_dialogue_core::graph::{ConnectionData, DialogueGraph, DialogueNode, NodeId};
+    /// use funkus_dialogue_core::{DialogueAsset, DialogueRunner};
+    ///
+    /// let mut graph = DialogueGraph::new(NodeId(1))
+    ///     .with_node(DialogueNode::choice(NodeId(1)))
+    ///     .with_node(DialogueNode::text(NodeId(2), "The mill burned down last year."))
+    ///     .with_node(DialogueNode::text(NodeId(3), "Farewell."));
+    /// let question = ConnectionData::new(Some("What happened?".into())).with_once(true);
+    /// graph.connect(NodeId(1), NodeId(2), question).unwrap();
+    /// graph.connect(NodeId(1), NodeId(3), ConnectionData::new(Some("Bye".into()))).unwrap();
+    /// let dialogue = DialogueAsset::new(graph);
+    ///
+    /// let mut runner = DialogueRunner::default();
+    /// runner.taken_choices.insert((NodeId(1), NodeId(2)));
+    /// let choices = runner.available_choices(&dialogue, NodeId(1));
+    /// assert_eq!(choices.len(), 1);
+    /// assert_eq!(choices[0].0, NodeId(3));
+    /// ```
+    pub fn available_choices<'a>(
+        &self,
+        dialogue: &'a DialogueAsset,
+        node_id: NodeId,
+    ) -> Vec<(NodeId, &'a ConnectionData)> {
+        dialogue
+            .graph
+            .get_connections(node_id)
+            .into_iter()
+            .filter(|(target_id, data)| {
+                !(data.once && self.taken_choices.contains(&(node_id, *target_id)))
+            })
+            .collect()
+    }
+
     /// Selects a choice option.
     ///
     /// This method updates the dialogue state to reflect that a choice has been selected.
@@ -379,7 +1153,9 @@ impl DialogueRunner {
     ///
     /// # Parameters
     ///
-    /// * `choice_index` - The index of the choice to select
+    /// * `dialogue` - The dialogue asset being processed
+    /// * `choice_index` - The index of the choice to select, among the
+    ///   [`available_choices`](Self::available_choices)
     ///
     /// # Returns
     ///
@@ -388,45 +1164,144 @@ impl DialogueRunner {
     /// # Errors
     ///
     /// This method can return `InvalidStateTransition` if a choice cannot be
-    /// selected in the current state.
+    /// selected in the current state, or `InvalidChoiceIndex` if the current node
+    /// has no such choice. The state is left unchanged on error.
     ///
     /// # Example
     ///
     /// ```rust
-    /// # use bevy::prelude::*;
-    /// # use funkus_dialogue::{DialogueRunner, DialogueState};
-    /// #
-    /// fn select_choice(
-    ///     mut dialogue_query: Query<&mut DialogueRunner>,
-    ///     keyboard_input: Res<Input<KeyCode>>,
-    /// ) {
-    ///     for mut runner in dialogue_query.iter_mut() {
-    ///         if runner.state == DialogueState::WaitingForChoice {
-    ///             // Select the first choice when '1' is pressed
-    ///             if keyboard_input.just_pressed(KeyCode::Key1) {
-    ///                 if let Err(err) = runner.select_choice(0) {
-    ///                     eprintln!("Error selecting choice: {}", err);
-    ///                 }
-    ///             }
-    ///         }
-    ///     }
-    /// }
+    /// use funkus_dialogue_core::graph::{ConnectionData, DialogueGraph, DialogueNode, NodeId};
+    /// use funkus_dialogue_core::{DialogueAsset, DialogueError, DialogueRunner, DialogueState};
+    ///
+    /// let mut graph = DialogueGraph::new(NodeId(1))
+    ///     .with_node(DialogueNode::choice(NodeId(1)).with_prompt("Ready?").unwrap())
+    ///     .with_node(DialogueNode::text(NodeId(2), "Let's go!"));
+    /// graph.connect(NodeId(1), NodeId(2), ConnectionData::new(Some("Yes".into()))).unwrap();
+    /// let dialogue = DialogueAsset::new(graph);
+    ///
+    /// let mut runner = DialogueRunner::default();
+    /// runner.start(&dialogue).unwrap();
+    /// assert!(matches!(
+    ///     runner.select_choice(&dialogue, 1),
+    ///     Err(DialogueError::InvalidChoiceIndex(1, 0))
+    /// ));
+    /// runner.select_choice(&dialogue, 0).unwrap();
+    /// assert_eq!(runner.state, DialogueState::ChoiceSelected(0));
     /// ```
-    pub fn select_choice(&mut self, choice_index: usize) -> DialogueResult<()> {
+    pub fn select_choice(
+        &mut self,
+        dialogue: &DialogueAsset,
+        choice_index: usize,
+    ) -> DialogueResult<()> {
         // Check if we can select a choice in the current state
         if !self.state.can_select_choice() {
             return Err(DialogueError::InvalidStateTransition {
-                from: self.state.name(),
-                action: "select_choice".to_string(),
+                from: self.state.clone(),
+                action: DialogueAction::SelectChoice,
             });
         }
 
+        // Taken once-only choices aren't offered anymore
+        let choices = self
+            .current_node_id
+            .map_or(0, |id| self.available_choices(dialogue, id).len());
+        if choice_index >= choices {
+            return Err(DialogueError::InvalidChoiceIndex(
+                choice_index,
+                choices.saturating_sub(1),
+            ));
+        }
+
         // Update the state to ChoiceSelected
         self.state = DialogueState::ChoiceSelected(choice_index);
 
         Ok(())
     }
 
+    /// Submits the player's input to the current input node.
+    ///
+    /// The input is checked against the node's rules, then stored trimmed into the
+    /// node's variable. The runner enters the first connected node whose condition
+    /// holds, so connections can branch on the input, e.g. `password == swordfish`.
+    /// The dialogue finishes if no connection matches.
+    ///
+    /// # Parameters
+    ///
+    /// * `dialogue` - The dialogue asset being processed
+    /// * `input` - The input submitted by the player
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidStateTransition` if the runner isn't waiting for an input, or
+    /// `InvalidInput` with the first rule the input breaks, or the errors of
+    /// [`advance`](Self::advance) if the matching node can't be entered. The runner
+    /// stays on the input node, and the variable is left as it was, when the input is
+    /// rejected.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use funkus_dialogue_core::{DialogueAsset, DialogueRunner};
+    /// #
+    /// fn enter_name(runner: &mut DialogueRunner, dialogue: &DialogueAsset) {
+    ///     if let Err(err) = runner.submit_input(dialogue, "Ada") {
+    ///         eprintln!("Name rejected: {}", err);
+    ///     }
+    /// }
+    /// ```
+    pub fn submit_input(&mut self, dialogue: &DialogueAsset, input: &str) -> DialogueResult<()> {
+        let Some(DialogueNode::Input {
+            id,
+            variable,
+            rules,
+            ..
+        }) = self
+            .current_node(dialogue)
+            .filter(|_| self.state == DialogueState::WaitingForInput)
+        else {
+            return Err(DialogueError::InvalidStateTransition {
+                from: self.state.clone(),
+                action: DialogueAction::SubmitInput,
+            });
+        };
+
+        if let Some(rule) = rules.iter().find(|rule| !rule.accepts(input)) {
+            return Err(DialogueError::InvalidInput {
+                node_id: *id,
+                rule: rule.clone(),
+            });
+        }
+
+        let previous = self
+            .variables
+            .insert(variable.clone(), input.trim().to_string());
+
+        let next_id = dialogue
+            .graph
+            .get_connections(*id)
+            .into_iter()
+            .find(|(_, data)| {
+                data.condition
+                    .as_deref()
+                    .is_none_or(|condition| self.check_condition(condition))
+            })
+            .map(|(target_id, _)| target_id);
+
+        match next_id {
+            Some(next_id) => self.enter_next(dialogue, next_id).inspect_err(|_| {
+                // The input wasn't taken after all
+                match previous {
+                    Some(value) => self.variables.insert(variable.clone(), value),
+                    None => self.variables.remove(variable),
+                };
+            }),
+            None => {
+                self.state = DialogueState::Finished;
+                Ok(())
+            }
+        }
+    }
+
     /// Gets the current node from the dialogue asset.
     ///
     /// # Parameters
@@ -493,6 +1368,19 @@ impl DialogueRunner {
         self.state == DialogueState::Finished
     }
 
+    /// Checks if the dialogue has been started and hasn't finished yet.
+    pub fn is_running(&self) -> bool {
+        !matches!(
+            self.state,
+            DialogueState::Inactive | DialogueState::Finished
+        )
+    }
+
+    /// Checks if the given node has been visited since the dialogue started.
+    pub fn has_visited(&self, id: NodeId) -> bool {
+        self.history.contains(&id)
+    }
+
     /// Stops the dialogue and returns to inactive state.
     ///
     /// This method resets the dialogue runner to its initial state,
@@ -515,3 +1403,733 @@ impl DialogueRunner {
         self.current_node_id = None;
     }
 }
+
+/// What entering nodes changes on a runner, kept to undo a failed
+/// [`DialogueRunner::enter_node`].
+struct EnterSnapshot {
+    current_node_id: Option<NodeId>,
+    state: DialogueState,
+    auto_advance_timer: Timer,
+    history_len: usize,
+    rng: ChaCha8Rng,
+    random_picks: HashMap<NodeId, Vec<NodeId>>,
+    line_counts: HashMap<NodeId, usize>,
+    shuffled_lines: HashMap<NodeId, Vec<usize>>,
+    current_line: usize,
+    hubs: Vec<NodeId>,
+    outcome: Option<String>,
+}
+
+impl EnterSnapshot {
+    fn take(runner: &DialogueRunner) -> Self {
+        Self {
+            current_node_id: runner.current_node_id,
+            state: runner.state.clone(),
+            auto_advance_timer: runner.auto_advance_timer.clone(),
+            history_len: runner.history.len(),
+            rng: runner.rng.clone(),
+            random_picks: runner.random_picks.clone(),
+            line_counts: runner.line_counts.clone(),
+            shuffled_lines: runner.shuffled_lines.clone(),
+            current_line: runner.current_line,
+            hubs: runner.hubs.clone(),
+            outcome: runner.outcome.clone(),
+        }
+    }
+
+    fn restore(self, runner: &mut DialogueRunner) {
+        runner.current_node_id = self.current_node_id;
+        runner.state = self.state;
+        runner.auto_advance_timer = self.auto_advance_timer;
+        runner.history.truncate(self.history_len);
+        runner.rng = self.rng;
+        runner.random_picks = self.random_picks;
+        runner.line_counts = self.line_counts;
+        runner.shuffled_lines = self.shuffled_lines;
+        runner.current_line = self.current_line;
+        runner.hubs = self.hubs;
+        runner.outcome = self.outcome;
+    }
+}
+
+/// Picks one of the candidates according to their weights.
+///
+/// Weights are summed as clamped `f64`s, so large weights can't add up to infinity.
+fn weighted_pick(rng: &mut ChaCha8Rng, candidates: &[(NodeId, f32)]) -> NodeId {
+    let weight_of = |weight: f32| f64::from(weight.min(f32::MAX));
+    let total: f64 = candidates
+        .iter()
+        .map(|&(_, weight)| weight_of(weight))
+        .sum();
+    let mut roll = rng.gen_range(0.0..total);
+    for &(target_id, weight) in candidates {
+        let weight = weight_of(weight);
+        if roll < weight {
+            return target_id;
+        }
+        roll -= weight;
+    }
+
+    // Rounding errors can leave a tiny remainder
+    candidates[candidates.len() - 1].0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ConnectionData, DialogueGraph, EntryPoint, InputRule};
+    use std::time::Duration;
+
+    fn create_test_dialogue() -> DialogueAsset {
+        let mut graph = DialogueGraph::new(NodeId(1))
+            .with_node(DialogueNode::text(NodeId(1), "Start"))
+            .with_node(DialogueNode::choice(NodeId(2)))
+            .with_node(DialogueNode::text(NodeId(3), "A"))
+            .with_node(DialogueNode::text(NodeId(4), "B"));
+        graph
+            .connect(NodeId(1), NodeId(2), ConnectionData::new(None))
+            .unwrap();
+        graph
+            .connect(
+                NodeId(2),
+                NodeId(3),
+                ConnectionData::new(Some("A".to_string())),
+            )
+            .unwrap();
+        graph
+            .connect(
+                NodeId(2),
+                NodeId(4),
+                ConnectionData::new(Some("B".to_string())),
+            )
+            .unwrap();
+        DialogueAsset::new(graph)
+    }
+
+    #[test]
+    fn test_reload_keeps_current_node() {
+        let mut dialogue = create_test_dialogue();
+        let mut runner = DialogueRunner::default();
+        runner.start(&dialogue).unwrap();
+        runner.advance(&dialogue).unwrap();
+        runner.select_choice(&dialogue, 1).unwrap();
+
+        // Editing text keeps the runner where it is
+        dialogue
+            .graph
+            .get_node_mut(NodeId(3))
+            .unwrap()
+            .set_text("Edited")
+            .unwrap();
+        assert_eq!(runner.reload(&dialogue), None);
+        assert_eq!(runner.current_node_id, Some(NodeId(2)));
+        assert_eq!(runner.state, DialogueState::ChoiceSelected(1));
+
+        // Removing the selected choice deselects it
+        dialogue.graph.disconnect(NodeId(2), NodeId(4)).unwrap();
+        assert_eq!(runner.reload(&dialogue), None);
+        assert_eq!(runner.state, DialogueState::WaitingForChoice);
+    }
+
+    #[test]
+    fn test_reload_falls_back_to_start() {
+        let mut dialogue = create_test_dialogue();
+        let mut runner = DialogueRunner::default();
+        runner.start(&dialogue).unwrap();
+        runner.advance(&dialogue).unwrap();
+
+        dialogue.graph.remove_node(NodeId(2)).unwrap();
+        assert_eq!(runner.reload(&dialogue), Some(NodeId(2)));
+        assert_eq!(runner.current_node_id, Some(NodeId(1)));
+        assert_eq!(runner.state, DialogueState::ShowingText);
+    }
+
+    #[test]
+    fn test_invalid_transition_is_structured() {
+        let dialogue = create_test_dialogue();
+        let mut runner = DialogueRunner::default();
+        runner.start(&dialogue).unwrap();
+        runner.advance(&dialogue).unwrap();
+
+        match runner.advance(&dialogue) {
+            Err(DialogueError::InvalidStateTransition { from, action }) => {
+                assert_eq!(from, DialogueState::WaitingForChoice);
+                assert_eq!(action, DialogueAction::Advance);
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_skip() {
+        let dialogue = create_test_dialogue();
+        let mut runner = DialogueRunner::default();
+        runner.start(&dialogue).unwrap();
+        runner.advance(&dialogue).unwrap();
+
+        // Skipping a choice node enters its first choice
+        assert_eq!(runner.skip(&dialogue), Some(NodeId(3)));
+        assert_eq!(runner.state, DialogueState::ShowingText);
+
+        // Skipping a node without connections finishes the dialogue
+        assert_eq!(runner.skip(&dialogue), None);
+        assert!(runner.is_finished());
+    }
+
+    fn create_random_dialogue(mode: RandomMode) -> DialogueAsset {
+        let mut graph = DialogueGraph::new(NodeId(1))
+            .with_node(DialogueNode::random(NodeId(1), mode))
+            .with_node(DialogueNode::text(NodeId(2), "A"))
+            .with_node(DialogueNode::text(NodeId(3), "B"))
+            .with_node(DialogueNode::text(NodeId(4), "C"));
+        for target in [NodeId(2), NodeId(3), NodeId(4)] {
+            graph
+                .connect(NodeId(1), target, ConnectionData::new(None))
+                .unwrap();
+        }
+        DialogueAsset::new(graph)
+    }
+
+    fn pick(runner: &mut DialogueRunner, dialogue: &DialogueAsset) -> NodeId {
+        runner.start(dialogue).unwrap();
+        assert_eq!(runner.state, DialogueState::ShowingText);
+        runner.current_node_id.unwrap()
+    }
+
+    #[test]
+    fn test_random_is_deterministic_with_seed() {
+        let dialogue = create_random_dialogue(RandomMode::Weighted);
+        let mut first = DialogueRunner::default().with_seed(7);
+        let mut second = DialogueRunner::default().with_seed(7);
+
+        for _ in 0..10 {
+            assert_eq!(pick(&mut first, &dialogue), pick(&mut second, &dialogue));
+        }
+        assert_eq!(first.history[0], NodeId(1));
+    }
+
+    #[test]
+    fn test_random_weights_and_conditions() {
+        let mut dialogue = create_random_dialogue(RandomMode::Weighted);
+        dialogue
+            .graph
+            .get_connection_mut(NodeId(1), NodeId(2))
+            .unwrap()
+            .weight = 0.0;
+        dialogue
+            .graph
+            .get_connection_mut(NodeId(1), NodeId(3))
+            .unwrap()
+            .condition = Some("weather == rain".to_string());
+        let mut runner = DialogueRunner::default().with_seed(1);
+
+        for _ in 0..10 {
+            assert_eq!(pick(&mut runner, &dialogue), NodeId(4));
+        }
+
+        runner
+            .variables
+            .insert("weather".to_string(), "rain".to_string());
+        let picks: Vec<_> = (0..20).map(|_| pick(&mut runner, &dialogue)).collect();
+        assert!(picks.contains(&NodeId(3)));
+        assert!(!picks.contains(&NodeId(2)));
+    }
+
+    #[test]
+    fn test_random_huge_weights() {
+        let mut dialogue = create_random_dialogue(RandomMode::Weighted);
+        for (target, weight) in [
+            (NodeId(2), 3e38),
+            (NodeId(3), 3e38),
+            (NodeId(4), f32::INFINITY),
+        ] {
+            dialogue
+                .graph
+                .get_connection_mut(NodeId(1), target)
+                .unwrap()
+                .weight = weight;
+        }
+        let mut runner = DialogueRunner::default().with_seed(3);
+
+        // The weights overflow f32 when summed, but picking still works
+        let picks: Vec<_> = (0..30).map(|_| pick(&mut runner, &dialogue)).collect();
+        assert!(picks.contains(&NodeId(2)));
+        assert!(picks.contains(&NodeId(4)));
+    }
+
+    #[test]
+    fn test_random_shuffle_and_cycle() {
+        let dialogue = create_random_dialogue(RandomMode::Shuffle);
+        let mut runner = DialogueRunner::default().with_seed(3);
+        let mut picks: Vec<_> = (0..3).map(|_| pick(&mut runner, &dialogue)).collect();
+        picks.sort_by_key(|id| id.0);
+        assert_eq!(picks, vec![NodeId(2), NodeId(3), NodeId(4)]);
+
+        let dialogue = create_random_dialogue(RandomMode::Cycle);
+        let mut runner = DialogueRunner::default();
+        let picks: Vec<_> = (0..4).map(|_| pick(&mut runner, &dialogue)).collect();
+        assert_eq!(picks, vec![NodeId(2), NodeId(3), NodeId(4), NodeId(2)]);
+    }
+
+    #[test]
+    fn test_random_without_candidates_finishes() {
+        let mut dialogue = create_random_dialogue(RandomMode::Weighted);
+        for target in [NodeId(2), NodeId(3), NodeId(4)] {
+            dialogue
+                .graph
+                .get_connection_mut(NodeId(1), target)
+                .unwrap()
+                .condition = Some("!met_guide".to_string());
+        }
+        let mut runner = DialogueRunner::default();
+        runner
+            .variables
+            .insert("met_guide".to_string(), "true".to_string());
+        runner.start(&dialogue).unwrap();
+        assert!(runner.is_finished());
+    }
+
+    #[test]
+    fn test_failed_start_leaves_runner_untouched() {
+        let dialogue = create_test_dialogue();
+        let mut runner = DialogueRunner::default();
+        runner.start(&dialogue).unwrap();
+        runner.advance(&dialogue).unwrap();
+
+        let mut broken = create_test_dialogue();
+        broken.graph.start_node = NodeId(9);
+        assert!(matches!(
+            runner.start(&broken),
+            Err(DialogueError::NodeNotFound(NodeId(9)))
+        ));
+        assert_eq!(runner.current_node_id, Some(NodeId(2)));
+        assert_eq!(runner.history, vec![NodeId(1), NodeId(2)]);
+        assert!(runner.is_running());
+    }
+
+    #[test]
+    fn test_random_loop_leaves_runner_untouched() {
+        let mut graph = DialogueGraph::new(NodeId(1))
+            .with_node(DialogueNode::text(NodeId(1), "Hello"))
+            .with_node(DialogueNode::choice(NodeId(2)))
+            .with_node(DialogueNode::random(NodeId(3), RandomMode::Cycle))
+            .with_node(DialogueNode::random(NodeId(4), RandomMode::Cycle));
+        graph
+            .connect(NodeId(1), NodeId(2), ConnectionData::new(None))
+            .unwrap();
+        graph
+            .connect(
+                NodeId(2),
+                NodeId(3),
+                ConnectionData::new(Some("Roll".to_string())).with_once(true),
+            )
+            .unwrap();
+        graph
+            .connect(NodeId(3), NodeId(4), ConnectionData::new(None))
+            .unwrap();
+        graph
+            .connect(NodeId(4), NodeId(3), ConnectionData::new(None))
+            .unwrap();
+        let dialogue = DialogueAsset::new(graph);
+
+        let mut runner = DialogueRunner::default();
+        runner.start(&dialogue).unwrap();
+        runner.advance(&dialogue).unwrap();
+        runner.select_choice(&dialogue, 0).unwrap();
+
+        assert!(matches!(
+            runner.advance(&dialogue),
+            Err(DialogueError::PassThroughLoop(_))
+        ));
+        assert_eq!(runner.current_node_id, Some(NodeId(2)));
+        assert_eq!(runner.state, DialogueState::ChoiceSelected(0));
+        assert_eq!(runner.history, vec![NodeId(1), NodeId(2)]);
+        assert!(runner.random_picks.is_empty());
+        assert!(runner.taken_choices.is_empty());
+
+        assert!(matches!(
+            runner.jump_to(&dialogue, NodeId(3)),
+            Err(DialogueError::PassThroughLoop(_))
+        ));
+        assert_eq!(runner.current_node_id, Some(NodeId(2)));
+    }
+
+    fn create_variant_dialogue(mode: TextVariantMode) -> DialogueAsset {
+        let greeting = DialogueNode::text(NodeId(1), "Hello")
+            .with_variants(mode, ["Again?", "You again."])
+            .unwrap();
+        let mut graph = DialogueGraph::new(NodeId(1))
+            .with_node(greeting)
+            .with_node(DialogueNode::text(NodeId(2), "Bye"));
+        graph
+            .connect(NodeId(1), NodeId(2), ConnectionData::new(None))
+            .unwrap();
+        DialogueAsset::new(graph)
+    }
+
+    fn greetings(mode: TextVariantMode, count: usize) -> Vec<String> {
+        let dialogue = create_variant_dialogue(mode);
+        let mut runner = DialogueRunner::default().with_seed(5);
+        (0..count)
+            .map(|_| {
+                runner.start(&dialogue).unwrap();
+                runner.current_text(&dialogue).unwrap().to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_text_variants() {
+        assert_eq!(
+            greetings(TextVariantMode::Sequence, 4),
+            ["Hello", "Again?", "You again.", "You again."]
+        );
+        assert_eq!(
+            greetings(TextVariantMode::Cycle, 4),
+            ["Hello", "Again?", "You again.", "Hello"]
+        );
+
+        let mut shuffled = greetings(TextVariantMode::Shuffle, 3);
+        shuffled.sort();
+        assert_eq!(shuffled, ["Again?", "Hello", "You again."]);
+    }
+
+    #[test]
+    fn test_once_variants_pass_through() {
+        assert_eq!(
+            greetings(TextVariantMode::Once, 4),
+            ["Hello", "Again?", "You again.", "Bye"]
+        );
+
+        // Without a next node, the dialogue finishes
+        let dialogue = DialogueAsset::new(
+            DialogueGraph::new(NodeId(1)).with_node(
+                DialogueNode::text(NodeId(1), "Psst!")
+                    .with_variants(TextVariantMode::Once, Vec::<String>::new())
+                    .unwrap(),
+            ),
+        );
+        let mut runner = DialogueRunner::default();
+        runner.start(&dialogue).unwrap();
+        assert_eq!(runner.state, DialogueState::ShowingText);
+        runner.start(&dialogue).unwrap();
+        assert!(runner.is_finished());
+    }
+
+    #[test]
+    fn test_advance_delay() {
+        let mut graph = DialogueGraph::new(NodeId(1))
+            .with_node(DialogueNode::text(NodeId(1), "Plain"))
+            .with_node(
+                DialogueNode::text(NodeId(2), "Quick")
+                    .with_advance(AdvanceTiming::After(0.5))
+                    .unwrap(),
+            )
+            .with_node(
+                DialogueNode::text(NodeId(3), "Held")
+                    .with_advance(AdvanceTiming::WaitForSignal)
+                    .unwrap(),
+            )
+            .with_node(DialogueNode::wait(NodeId(4), 1.5));
+        for (from, to) in [(1, 2), (2, 3), (3, 4)] {
+            graph
+                .connect(NodeId(from), NodeId(to), ConnectionData::new(None))
+                .unwrap();
+        }
+        let dialogue = DialogueAsset::new(graph);
+
+        let mut runner = DialogueRunner {
+            auto_advance_time: 3.0,
+            ..Default::default()
+        };
+        runner.start(&dialogue).unwrap();
+        assert_eq!(runner.advance_delay(&dialogue), None);
+        runner.auto_advance = true;
+        assert_eq!(runner.advance_delay(&dialogue), Some(3.0));
+
+        runner.advance(&dialogue).unwrap();
+        assert_eq!(runner.advance_delay(&dialogue), Some(0.5));
+        runner.advance(&dialogue).unwrap();
+        assert_eq!(runner.advance_delay(&dialogue), None);
+
+        // Wait nodes wait, but can be advanced early
+        runner.advance(&dialogue).unwrap();
+        assert_eq!(runner.state, DialogueState::Waiting);
+        assert_eq!(runner.advance_delay(&dialogue), Some(1.5));
+        runner.advance(&dialogue).unwrap();
+        assert!(runner.is_finished());
+    }
+
+    #[test]
+    fn test_choice_timeout() {
+        let mut dialogue = create_test_dialogue();
+        dialogue
+            .graph
+            .update_node(
+                NodeId(2),
+                DialogueNode::choice(NodeId(2))
+                    .with_timeout(4.0, 1)
+                    .unwrap(),
+            )
+            .unwrap();
+        let mut runner = DialogueRunner::default();
+        runner.start(&dialogue).unwrap();
+        assert_eq!(runner.choice_time_left(&dialogue), None);
+
+        runner.advance(&dialogue).unwrap();
+        runner
+            .auto_advance_timer
+            .set_duration(Duration::from_secs(4));
+        runner.auto_advance_timer.tick(Duration::from_secs(1));
+        assert_eq!(runner.choice_time_left(&dialogue), Some(3.0));
+
+        // Without a selection, the default choice is taken
+        assert_eq!(runner.time_out_choice(&dialogue).unwrap(), 1);
+        assert_eq!(runner.current_node_id, Some(NodeId(4)));
+
+        // A choice that isn't offered can't be selected, so the default is still taken
+        runner.start(&dialogue).unwrap();
+        runner.advance(&dialogue).unwrap();
+        assert!(matches!(
+            runner.select_choice(&dialogue, 2),
+            Err(DialogueError::InvalidChoiceIndex(2, 1))
+        ));
+        assert_eq!(runner.state, DialogueState::WaitingForChoice);
+        assert_eq!(runner.time_out_choice(&dialogue).unwrap(), 1);
+
+        // A selected choice is kept
+        runner.start(&dialogue).unwrap();
+        runner.advance(&dialogue).unwrap();
+        runner.select_choice(&dialogue, 0).unwrap();
+        assert_eq!(runner.time_out_choice(&dialogue).unwrap(), 0);
+        assert_eq!(runner.current_node_id, Some(NodeId(3)));
+
+        // A choice node without choices reports an error and stays put
+        let graph = DialogueGraph::new(NodeId(1)).with_node(
+            DialogueNode::choice(NodeId(1))
+                .with_timeout(4.0, 0)
+                .unwrap(),
+        );
+        let dialogue = DialogueAsset::new(graph);
+        runner.start(&dialogue).unwrap();
+        assert!(matches!(
+            runner.time_out_choice(&dialogue),
+            Err(DialogueError::NoChoices(NodeId(1)))
+        ));
+        assert_eq!(runner.state, DialogueState::WaitingForChoice);
+    }
+
+    #[test]
+    fn test_submit_input() {
+        let mut graph = DialogueGraph::new(NodeId(1))
+            .with_node(
+                DialogueNode::input(NodeId(1), "password")
+                    .with_rules(vec![InputRule::NotEmpty, InputRule::MaxLength(10)])
+                    .unwrap(),
+            )
+            .with_node(DialogueNode::text(NodeId(2), "Come in"))
+            .with_node(DialogueNode::text(NodeId(3), "Go away"));
+        graph
+            .connect(
+                NodeId(1),
+                NodeId(2),
+                ConnectionData::new(None).with_condition("password == swordfish"),
+            )
+            .unwrap();
+        graph
+            .connect(NodeId(1), NodeId(3), ConnectionData::new(None))
+            .unwrap();
+        let dialogue = DialogueAsset::new(graph);
+        let mut runner = DialogueRunner::default();
+        runner.start(&dialogue).unwrap();
+        assert_eq!(runner.state, DialogueState::WaitingForInput);
+        assert!(runner.advance(&dialogue).is_err());
+
+        // Rejected inputs keep the runner on the node
+        assert!(matches!(
+            runner.submit_input(&dialogue, "  "),
+            Err(DialogueError::InvalidInput {
+                rule: InputRule::NotEmpty,
+                ..
+            })
+        ));
+        assert_eq!(runner.state, DialogueState::WaitingForInput);
+
+        runner.submit_input(&dialogue, " Swordfish ").unwrap();
+        assert_eq!(runner.variables["password"], "Swordfish");
+        assert_eq!(runner.current_node_id, Some(NodeId(3)));
+
+        runner.start(&dialogue).unwrap();
+        runner.submit_input(&dialogue, "swordfish").unwrap();
+        assert_eq!(runner.current_node_id, Some(NodeId(2)));
+        assert!(runner.submit_input(&dialogue, "swordfish").is_err());
+    }
+
+    #[test]
+    fn test_hub_with_once_choices() {
+        // Hub 1 asks about 2 or 3 once each, both go back through return node 4
+        let mut graph = DialogueGraph::new(NodeId(1))
+            .with_node(DialogueNode::choice(NodeId(1)).with_hub(true).unwrap())
+            .with_node(DialogueNode::text(NodeId(2), "About the mill"))
+            .with_node(DialogueNode::text(NodeId(3), "About the well"))
+            .with_node(DialogueNode::return_to_hub(NodeId(4)));
+        for target in [NodeId(2), NodeId(3)] {
+            graph
+                .connect(NodeId(1), target, ConnectionData::new(None).with_once(true))
+                .unwrap();
+            graph
+                .connect(target, NodeId(4), ConnectionData::new(None))
+                .unwrap();
+        }
+        let dialogue = DialogueAsset::new(graph);
+        let mut runner = DialogueRunner::default();
+        runner.start(&dialogue).unwrap();
+
+        runner.select_choice(&dialogue, 0).unwrap();
+        runner.advance(&dialogue).unwrap();
+        assert_eq!(runner.current_node_id, Some(NodeId(2)));
+        runner.advance(&dialogue).unwrap();
+        assert_eq!(runner.current_node_id, Some(NodeId(1)));
+        let choices = runner.available_choices(&dialogue, NodeId(1));
+        assert_eq!(choices.len(), 1);
+        assert_eq!(choices[0].0, NodeId(3));
+
+        // The remaining choice is now the first one
+        runner.select_choice(&dialogue, 0).unwrap();
+        runner.advance(&dialogue).unwrap();
+        assert_eq!(runner.current_node_id, Some(NodeId(3)));
+
+        // The exhausted hub is left and there is no other hub to go back to
+        runner.advance(&dialogue).unwrap();
+        assert_eq!(runner.state, DialogueState::Finished);
+        assert!(runner.hubs.is_empty());
+
+        // Taken choices are remembered across dialogues
+        runner.start(&dialogue).unwrap();
+        assert_eq!(runner.state, DialogueState::Finished);
+
+        // Until they are cleared
+        runner.taken_choices.clear();
+        runner.start(&dialogue).unwrap();
+        assert_eq!(runner.state, DialogueState::WaitingForChoice);
+        assert_eq!(runner.available_choices(&dialogue, NodeId(1)).len(), 2);
+    }
+
+    #[test]
+    fn test_return_to_outer_hub() {
+        // Hub 1 leads to hub 2, whose only choice returns through node 4
+        let mut graph = DialogueGraph::new(NodeId(1))
+            .with_node(DialogueNode::choice(NodeId(1)).with_hub(true).unwrap())
+            .with_node(DialogueNode::choice(NodeId(2)).with_hub(true).unwrap())
+            .with_node(DialogueNode::text(NodeId(3), "Done"))
+            .with_node(DialogueNode::return_to_hub(NodeId(4)));
+        graph
+            .connect(NodeId(1), NodeId(2), ConnectionData::new(None))
+            .unwrap();
+        graph
+            .connect(NodeId(1), NodeId(3), ConnectionData::new(None))
+            .unwrap();
+        graph
+            .connect(
+                NodeId(2),
+                NodeId(4),
+                ConnectionData::new(None).with_once(true),
+            )
+            .unwrap();
+        let dialogue = DialogueAsset::new(graph);
+        let mut runner = DialogueRunner::default();
+        runner.start(&dialogue).unwrap();
+        runner.select_choice(&dialogue, 0).unwrap();
+        runner.advance(&dialogue).unwrap();
+        assert_eq!(runner.hubs, vec![NodeId(1), NodeId(2)]);
+
+        runner.select_choice(&dialogue, 0).unwrap();
+        runner.advance(&dialogue).unwrap();
+        assert_eq!(runner.current_node_id, Some(NodeId(1)));
+        assert_eq!(runner.hubs, vec![NodeId(1)]);
+    }
+
+    #[test]
+    fn test_end_outcome() {
+        let mut graph = DialogueGraph::new(NodeId(1))
+            .with_node(DialogueNode::text(NodeId(1), "Will you help us?"))
+            .with_node(
+                DialogueNode::end(NodeId(2))
+                    .with_outcome("quest_accepted")
+                    .unwrap(),
+            );
+        graph
+            .connect(NodeId(1), NodeId(2), ConnectionData::new(None))
+            .unwrap();
+        let dialogue = DialogueAsset::new(graph);
+        let mut runner = DialogueRunner::default();
+        runner.start(&dialogue).unwrap();
+        assert_eq!(runner.outcome, None);
+
+        runner.advance(&dialogue).unwrap();
+        assert_eq!(runner.state, DialogueState::Finished);
+        assert_eq!(runner.current_node_id, Some(NodeId(2)));
+        assert_eq!(runner.outcome.as_deref(), Some("quest_accepted"));
+
+        // Restarting forgets the outcome
+        runner.start(&dialogue).unwrap();
+        assert_eq!(runner.outcome, None);
+    }
+
+    #[test]
+    fn test_entry_points() {
+        let graph = DialogueGraph::new(NodeId(1))
+            .with_node(DialogueNode::text(NodeId(1), "Hello, stranger."))
+            .with_node(DialogueNode::text(NodeId(2), "Back again?"))
+            .with_node(DialogueNode::text(NodeId(3), "Thanks for your help!"))
+            .with_entry_point(EntryPoint::new("returning", NodeId(2)).with_condition("met"))
+            .with_entry_point(EntryPoint::new("quest_done", NodeId(3)));
+        let dialogue = DialogueAsset::new(graph);
+        let mut runner = DialogueRunner::default();
+
+        runner.start(&dialogue).unwrap();
+        assert_eq!(runner.current_node_id, Some(NodeId(1)));
+        assert_eq!(runner.entry_point, None);
+
+        // The first entry point whose condition holds is picked automatically
+        runner
+            .variables
+            .insert("met".to_string(), "true".to_string());
+        runner.start(&dialogue).unwrap();
+        assert_eq!(runner.current_node_id, Some(NodeId(2)));
+        assert_eq!(runner.entry_point.as_deref(), Some("returning"));
+
+        runner.start_at(&dialogue, "quest_done").unwrap();
+        assert_eq!(runner.current_node_id, Some(NodeId(3)));
+        assert_eq!(runner.entry_point.as_deref(), Some("quest_done"));
+
+        // Unknown entry points leave the runner untouched
+        assert!(matches!(
+            runner.start_at(&dialogue, "missing"),
+            Err(DialogueError::UnknownEntryPoint(name)) if name == "missing"
+        ));
+        assert_eq!(runner.current_node_id, Some(NodeId(3)));
+    }
+
+    #[test]
+    fn test_jump_to() {
+        let dialogue = create_test_dialogue();
+        let mut runner = DialogueRunner::default();
+
+        // Jumping into a dialogue that isn't running starts it
+        runner.jump_to(&dialogue, NodeId(2)).unwrap();
+        assert_eq!(runner.state, DialogueState::WaitingForChoice);
+        assert_eq!(runner.history, vec![NodeId(2)]);
+
+        runner.jump_to(&dialogue, NodeId(4)).unwrap();
+        assert_eq!(runner.state, DialogueState::ShowingText);
+        assert_eq!(runner.history, vec![NodeId(2), NodeId(4)]);
+
+        assert!(matches!(
+            runner.jump_to(&dialogue, NodeId(9)),
+            Err(DialogueError::NodeNotFound(NodeId(9)))
+        ));
+        assert_eq!(runner.current_node_id, Some(NodeId(4)));
+        assert_eq!(runner.state, DialogueState::ShowingText);
+    }
+}
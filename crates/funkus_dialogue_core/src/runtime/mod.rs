@@ -41,6 +41,7 @@
 //!     start_events.send(StartDialogue {
 //!         entity,
 //!         dialogue_handle,
+//!         entry_point: None,
 //!     });
 //! }
 //! ```
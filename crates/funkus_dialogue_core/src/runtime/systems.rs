@@ -5,7 +5,20 @@
 
 use bevy::prelude::*;
 
-use crate::asset::DialogueAsset;
+use bevy::asset::LoadState;
+use std::time::Duration;
+
+use crate::asset::{CharacterDatabase, DialogueAsset, DialogueCharacters};
+use crate::error::DialogueError;
+use crate::events::{
+    AdvanceDialogue, DialogueChoiceMade, DialogueChoiceTimedOut, DialogueEnded,
+    DialogueErrorOccurred, DialogueNodeActivated, DialogueNotifications, DialogueReloadWarning,
+    DialogueStarted, JumpDialogue, StartDialogue, SubmitDialogueInput,
+};
+use crate::graph::{
+    CustomNodeAdvance, DialogueNode, DialogueNodeKind, DialogueNodeRegistry, NodeId,
+};
+use crate::runtime::DialogueErrorPolicy;
 use crate::runtime::DialogueRunner;
 use crate::runtime::DialogueState;
 
@@ -13,9 +26,15 @@ use crate::runtime::DialogueState;
 ///
 /// This system is responsible for:
 /// - Ticking auto-advance timers
-/// - Auto-advancing text nodes when the timer completes
+/// - Auto-advancing text and wait nodes when the timer completes, see
+///   [`DialogueRunner::advance_delay`]
+/// - Taking the default choice of timed choices that ran out of time
 /// - Handling other state updates
 ///
+/// Nodes entered by auto-advancing are notified like those entered through
+/// `AdvanceDialogue`. Errors while auto-advancing are handled according to the runner's
+/// [`DialogueErrorPolicy`].
+///
 /// Note: The system automatically skips runners with inactive state or
 /// runners whose dialogue assets haven't been loaded yet. It will silently
 /// continue processing other runners without errors.
@@ -24,6 +43,7 @@ use crate::runtime::DialogueState;
 ///
 /// * `time` - The Bevy time resource for delta time
 /// * `dialogue_assets` - Assets resource containing loaded dialogue assets
+/// * `notifications` - Sends dialogue notifications as events and triggers
 /// * `runner_query` - Query for DialogueRunner components
 ///
 /// # Example
@@ -39,9 +59,10 @@ use crate::runtime::DialogueState;
 pub fn update_dialogue_runners(
     time: Res<Time>,
     dialogue_assets: Res<Assets<DialogueAsset>>,
-    mut runner_query: Query<&mut DialogueRunner>,
+    mut notifications: DialogueNotifications,
+    mut runner_query: Query<(Entity, &mut DialogueRunner)>,
 ) {
-    for mut runner in runner_query.iter_mut() {
+    for (entity, mut runner) in runner_query.iter_mut() {
         // Skip inactive runners
         if runner.state == DialogueState::Inactive {
             continue;
@@ -53,20 +74,134 @@ pub fn update_dialogue_runners(
             continue;
         };
 
-        // Auto-advance text nodes if enabled
-        if runner.state == DialogueState::ShowingText && runner.auto_advance {
+        // Auto-advance nodes that move on by themselves
+        if let Some(delay) = runner.advance_delay(dialogue) {
+            // Follow changes of the delay, such as a new `auto_advance_time`
+            let duration = Duration::try_from_secs_f32(delay).unwrap_or_default();
+            runner.auto_advance_timer.set_duration(duration);
+            runner.auto_advance_timer.tick(time.delta());
+
+            if runner.auto_advance_timer.finished() {
+                let old_node_id = runner.current_node_id;
+                match runner.advance(dialogue) {
+                    Ok(()) => {
+                        notify_advanced(entity, &runner, dialogue, old_node_id, &mut notifications)
+                    }
+                    Err(err) => {
+                        handle_runner_error(entity, &mut runner, dialogue, err, &mut notifications)
+                    }
+                }
+            }
+        }
+
+        // Time out choices that have a time limit
+        if let Some(timeout) = runner.choice_timeout(dialogue) {
+            let duration = Duration::try_from_secs_f32(timeout).unwrap_or_default();
+            runner.auto_advance_timer.set_duration(duration);
             runner.auto_advance_timer.tick(time.delta());
 
             if runner.auto_advance_timer.finished() {
-                if let Err(err) = runner.advance(dialogue) {
-                    error!("Error advancing dialogue: {}", err);
-                    runner.state = DialogueState::Error(err.to_string());
+                let old_node_id = runner.current_node_id;
+                match runner.time_out_choice(dialogue) {
+                    Ok(choice_index) => {
+                        if let Some(node_id) = old_node_id {
+                            notifications.choice_timed_out(DialogueChoiceTimedOut {
+                                entity,
+                                node_id,
+                                choice_index,
+                            });
+                        }
+                        notify_advanced(entity, &runner, dialogue, old_node_id, &mut notifications);
+                    }
+                    Err(err) => {
+                        handle_runner_error(entity, &mut runner, dialogue, err, &mut notifications)
+                    }
                 }
             }
         }
     }
 }
 
+/// Sends the notifications for a runner that advanced from a node.
+fn notify_advanced(
+    entity: Entity,
+    runner: &DialogueRunner,
+    dialogue: &DialogueAsset,
+    old_node_id: Option<NodeId>,
+    notifications: &mut DialogueNotifications,
+) {
+    if runner.state == DialogueState::Finished {
+        // Send dialogue ended event
+        notifications.ended(DialogueEnded {
+            entity,
+            normal_exit: true,
+            outcome: runner.outcome.clone(),
+        });
+    } else if runner.current_node_id != old_node_id {
+        // Send node activated event
+        if let Some(event) = DialogueNodeActivated::from_runner(entity, dialogue, runner) {
+            notifications.node_activated(event);
+        }
+    }
+}
+
+/// Reports a dialogue that couldn't start.
+fn report_start_failed(
+    entity: Entity,
+    error: DialogueError,
+    start_failed_events: &mut EventWriter<crate::events::DialogueStartFailed>,
+    notifications: &mut DialogueNotifications,
+) {
+    error!("Failed to start dialogue on {:?}: {}", entity, error);
+    start_failed_events.send(crate::events::DialogueStartFailed {
+        entity,
+        error: error.clone(),
+    });
+    notifications.error_occurred(DialogueErrorOccurred { entity, error });
+}
+
+/// Reports an error of a dialogue runner and applies its [`DialogueErrorPolicy`].
+fn handle_runner_error(
+    entity: Entity,
+    runner: &mut DialogueRunner,
+    dialogue: &DialogueAsset,
+    error: DialogueError,
+    notifications: &mut DialogueNotifications,
+) {
+    error!("Dialogue error on {:?}: {}", entity, error);
+    notifications.error_occurred(DialogueErrorOccurred { entity, error });
+
+    match runner.error_policy {
+        DialogueErrorPolicy::Stop => {
+            runner.stop();
+            notifications.ended(DialogueEnded {
+                entity,
+                normal_exit: false,
+                outcome: None,
+            });
+        }
+        DialogueErrorPolicy::Stay => {
+            // Don't retry automatic advances every frame
+            runner.auto_advance_timer.reset();
+            runner.auto_advance_timer.pause();
+        }
+        DialogueErrorPolicy::Skip => {
+            runner.skip(dialogue);
+            // Skipping can also finish the dialogue, e.g. on an end node
+            if runner.state == DialogueState::Finished {
+                notifications.ended(DialogueEnded {
+                    entity,
+                    normal_exit: false,
+                    outcome: runner.outcome.clone(),
+                });
+            } else if let Some(event) = DialogueNodeActivated::from_runner(entity, dialogue, runner)
+            {
+                notifications.node_activated(event);
+            }
+        }
+    }
+}
+
 /// System set for dialogue processing.
 ///
 /// This system set groups all dialogue-related systems to allow for
@@ -88,83 +223,156 @@ pub fn update_dialogue_runners(
 #[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
 pub struct DialogueSystemSet;
 
+/// Resource holding the start requests whose dialogue asset isn't loaded yet.
+///
+/// Requests are processed by [`handle_dialogue_events`] every frame, in the order
+/// they were sent.
+#[derive(Resource, Debug, Default)]
+pub struct PendingDialogueStarts(pub Vec<StartDialogue>);
+
 /// System for handling dialogue events.
 ///
 /// This system processes all dialogue-related events, including:
 /// - Starting dialogues
 /// - Stopping dialogues
-/// - Advancing to the next node
 /// - Selecting choices
+/// - Advancing to the next node
 ///
 /// It also sends appropriate events to notify other systems about
-/// dialogue state changes.
+/// dialogue state changes. Notifications are triggered on the dialogue entity too,
+/// see [`DialogueNotifications`].
+///
+/// Dialogues are started as soon as their asset is loaded, so a `StartDialogue`
+/// can be sent right after `asset_server.load`. Entities without a
+/// `DialogueRunner` get one inserted when the dialogue starts. A `StartDialogue`
+/// replaces the one still waiting for the same entity. If the asset fails to load,
+/// the handle isn't being loaded, the dialogue can't be entered or the entity is
+/// despawned before the dialogue starts, a `DialogueStartFailed` event is sent
+/// instead. A dialogue that finishes as soon as
+/// it starts, e.g. on an end node, only sends a `DialogueEnded` event.
+///
+/// Choices are selected before dialogues are advanced, so a `SelectDialogueChoice`
+/// and an `AdvanceDialogue` sent in the same frame take the selected choice.
+///
+/// Failed actions are reported with a `DialogueErrorOccurred` event and handled
+/// according to the runner's [`DialogueErrorPolicy`], except for the selection of a
+/// choice that isn't offered, which leaves the runner waiting for another choice.
 ///
 /// # System Parameters
 ///
 /// * `commands` - Bevy commands for entity management
 /// * `dialogue_assets` - Assets resource containing loaded dialogue assets
+/// * `asset_server` - Asset server used to detect dialogue assets that failed to load
+/// * `pending_starts` - Start requests waiting for their dialogue asset to load
 /// * `start_events` - EventReader for StartDialogue events
 /// * `stop_events` - EventReader for StopDialogue events
 /// * `advance_events` - EventReader for AdvanceDialogue events
 /// * `select_events` - EventReader for SelectDialogueChoice events
-/// * `node_activated_events` - EventWriter for DialogueNodeActivated events
-/// * `dialogue_started_events` - EventWriter for DialogueStarted events
-/// * `dialogue_ended_events` - EventWriter for DialogueEnded events
-/// * `dialogue_choice_events` - EventWriter for DialogueChoiceMade events
+/// * `notifications` - Sends dialogue notifications as events and triggers
+/// * `start_failed_events` - EventWriter for DialogueStartFailed events
 /// * `runner_query` - Query for DialogueRunner components
 pub fn handle_dialogue_events(
     mut commands: Commands,
     dialogue_assets: Res<Assets<DialogueAsset>>,
+    asset_server: Res<AssetServer>,
+    mut pending_starts: ResMut<PendingDialogueStarts>,
     mut start_events: EventReader<crate::events::StartDialogue>,
     mut stop_events: EventReader<crate::events::StopDialogue>,
     mut advance_events: EventReader<crate::events::AdvanceDialogue>,
     mut select_events: EventReader<crate::events::SelectDialogueChoice>,
-    mut node_activated_events: EventWriter<crate::events::DialogueNodeActivated>,
-    mut dialogue_started_events: EventWriter<crate::events::DialogueStarted>,
-    mut dialogue_ended_events: EventWriter<crate::events::DialogueEnded>,
-    mut dialogue_choice_events: EventWriter<crate::events::DialogueChoiceMade>,
+    mut notifications: DialogueNotifications,
+    mut start_failed_events: EventWriter<crate::events::DialogueStartFailed>,
     mut runner_query: Query<&mut DialogueRunner>,
 ) {
-    // Handle start dialogue events
+    // Queue start dialogue events until their asset is loaded. A newer request replaces
+    // the one still waiting for the same entity, so a runner starts once per frame
     for ev in start_events.read() {
-        if let Ok(mut runner) = runner_query.get_mut(ev.entity) {
-            // Set the dialogue handle
-            runner.dialogue_handle = ev.dialogue_handle.clone();
-
-            // Get the dialogue asset
-            if let Some(dialogue) = dialogue_assets.get(&ev.dialogue_handle) {
-                // Start the dialogue
-                runner.start(dialogue);
+        pending_starts.0.retain(|start| start.entity != ev.entity);
+        pending_starts.0.push(ev.clone());
+    }
 
-                // Send node activated event for the start node
-                if let Some(node_id) = runner.current_node_id {
-                    node_activated_events.send(crate::events::DialogueNodeActivated {
-                        entity: ev.entity,
-                        node_id,
-                    });
+    // Handle pending starts
+    pending_starts.0.retain(|ev| {
+        // Drop requests for entities despawned while their asset was loading
+        if commands.get_entity(ev.entity).is_none() {
+            report_start_failed(
+                ev.entity,
+                DialogueError::EntityNotFound(ev.entity),
+                &mut start_failed_events,
+                &mut notifications,
+            );
+            return false;
+        }
 
-                    // Send dialogue started event
-                    dialogue_started_events.send(crate::events::DialogueStarted {
-                        entity: ev.entity,
-                        start_node_id: node_id,
-                    });
-                }
+        let Some(dialogue) = dialogue_assets.get(&ev.dialogue_handle) else {
+            // Keep waiting while the asset loads, other handles will never get an asset
+            let load_state = asset_server.get_load_state(&ev.dialogue_handle);
+            if !matches!(load_state, Some(LoadState::Loading)) {
+                report_start_failed(
+                    ev.entity,
+                    DialogueError::AssetNotLoaded,
+                    &mut start_failed_events,
+                    &mut notifications,
+                );
+                return false;
             }
-        } else {
-            // Create a new dialogue runner component
-            commands
-                .entity(ev.entity)
-                .insert(DialogueRunner::new(ev.dialogue_handle.clone()));
+            return true;
+        };
+
+        // Use the existing runner, or create a new one that is inserted once started
+        let mut existing = runner_query.get_mut(ev.entity).ok();
+        let mut created = None;
+        let runner = match existing.as_deref_mut() {
+            Some(runner) => runner,
+            None => created.insert(DialogueRunner::new(ev.dialogue_handle.clone())),
+        };
+
+        let result = match &ev.entry_point {
+            Some(entry_point) => runner.start_at(dialogue, entry_point),
+            None => runner.start(dialogue),
+        };
+        if let Err(error) = result {
+            report_start_failed(
+                ev.entity,
+                error,
+                &mut start_failed_events,
+                &mut notifications,
+            );
+            return false;
         }
-    }
+        runner.dialogue_handle = ev.dialogue_handle.clone();
+
+        // Send dialogue started event, unless starting already finished the dialogue,
+        // e.g. on an end node
+        if let (Some(node_id), false) = (runner.current_node_id, runner.is_finished()) {
+            notifications.started(DialogueStarted {
+                entity: ev.entity,
+                start_node_id: node_id,
+                entry_point: runner.entry_point.clone(),
+            });
+        }
+
+        // Send node activated event for the start node, or ended event
+        notify_advanced(ev.entity, runner, dialogue, None, &mut notifications);
+
+        if let Some(runner) = created {
+            commands.entity(ev.entity).try_insert(runner);
+        }
+
+        false
+    });
 
     // Handle stop dialogue events
     for ev in stop_events.read() {
+        // A stopped dialogue no longer waits to start
+        pending_starts.0.retain(|start| start.entity != ev.entity);
+
         if let Ok(mut runner) = runner_query.get_mut(ev.entity) {
             // Send dialogue ended event
-            dialogue_ended_events.send(crate::events::DialogueEnded {
+            notifications.ended(DialogueEnded {
                 entity: ev.entity,
                 normal_exit: false,
+                outcome: None,
             });
 
             // Stop the dialogue
@@ -172,6 +380,57 @@ pub fn handle_dialogue_events(
         }
     }
 
+    // Handle select choice events first, so a choice selected and confirmed in the
+    // same frame is taken
+    for ev in select_events.read() {
+        if let Ok(mut runner) = runner_query.get_mut(ev.entity) {
+            // Allow choice selection while in either WaitingForChoice or ChoiceSelected state
+            if runner.state == DialogueState::WaitingForChoice
+                || matches!(runner.state, DialogueState::ChoiceSelected(_))
+            {
+                // Get the current node id
+                let Some(node_id) = runner.current_node_id else {
+                    continue;
+                };
+
+                let Some(dialogue) = dialogue_assets.get(&runner.dialogue_handle) else {
+                    continue;
+                };
+
+                // Select the choice - this now also updates the state to ChoiceSelected
+                match runner.select_choice(dialogue, ev.choice_index) {
+                    Ok(()) => {}
+                    Err(error @ DialogueError::InvalidChoiceIndex(..)) => {
+                        // Keep waiting for a valid choice, and keep a timed choice counting down
+                        debug!("Choice rejected on {:?}: {}", ev.entity, error);
+                        notifications.error_occurred(DialogueErrorOccurred {
+                            entity: ev.entity,
+                            error,
+                        });
+                        continue;
+                    }
+                    Err(err) => {
+                        handle_runner_error(
+                            ev.entity,
+                            &mut runner,
+                            dialogue,
+                            err,
+                            &mut notifications,
+                        );
+                        continue;
+                    }
+                }
+
+                // Send choice made event
+                notifications.choice_made(DialogueChoiceMade {
+                    entity: ev.entity,
+                    node_id,
+                    choice_index: ev.choice_index,
+                });
+            }
+        }
+    }
+
     // Handle advance dialogue events
     for ev in advance_events.read() {
         if let Ok(mut runner) = runner_query.get_mut(ev.entity) {
@@ -181,58 +440,352 @@ pub fn handle_dialogue_events(
 
                 // Advance the dialogue
                 match runner.advance(dialogue) {
-                    Ok(()) => {
-                        if runner.state == DialogueState::Finished {
-                            // Send dialogue ended event
-                            dialogue_ended_events.send(crate::events::DialogueEnded {
-                                entity: ev.entity,
-                                normal_exit: true,
-                            });
-                        } else if runner.current_node_id != old_node_id {
-                            // Send node activated event
-                            if let Some(node_id) = runner.current_node_id {
-                                node_activated_events.send(crate::events::DialogueNodeActivated {
-                                    entity: ev.entity,
-                                    node_id,
-                                });
-                            }
-                        }
-                    }
+                    Ok(()) => notify_advanced(
+                        ev.entity,
+                        &runner,
+                        dialogue,
+                        old_node_id,
+                        &mut notifications,
+                    ),
                     Err(err) => {
-                        error!("Error advancing dialogue: {}", err);
-                        runner.state = DialogueState::Error(err.to_string());
+                        handle_runner_error(
+                            ev.entity,
+                            &mut runner,
+                            dialogue,
+                            err,
+                            &mut notifications,
+                        );
                     }
                 }
             }
         }
     }
+}
 
-    // Handle select choice events
-    for ev in select_events.read() {
-        if let Ok(mut runner) = runner_query.get_mut(ev.entity) {
-            // Allow choice selection while in either WaitingForChoice or ChoiceSelected state
-            if runner.state == DialogueState::WaitingForChoice
-                || matches!(runner.state, DialogueState::ChoiceSelected(_))
-            {
-                // Get the current node id
-                let Some(node_id) = runner.current_node_id else {
-                    continue;
-                };
+/// System that submits player inputs to input nodes.
+///
+/// A valid input moves the dialogue on, and the node it enters is notified like with
+/// `AdvanceDialogue`. An input that breaks one of the node's rules is reported as a
+/// [`DialogueErrorOccurred`] but leaves the runner waiting for another input, as
+/// the player is expected to fix it. Other errors are handled according to the
+/// runner's [`DialogueErrorPolicy`].
+///
+/// # System Parameters
+///
+/// * `dialogue_assets` - Assets resource containing loaded dialogue assets
+/// * `submit_events` - EventReader for SubmitDialogueInput events
+/// * `notifications` - Sends dialogue notifications as events and triggers
+/// * `runner_query` - Query for DialogueRunner components
+pub fn handle_input_events(
+    dialogue_assets: Res<Assets<DialogueAsset>>,
+    mut submit_events: EventReader<SubmitDialogueInput>,
+    mut notifications: DialogueNotifications,
+    mut runner_query: Query<&mut DialogueRunner>,
+) {
+    for ev in submit_events.read() {
+        let Ok(mut runner) = runner_query.get_mut(ev.entity) else {
+            continue;
+        };
+        let Some(dialogue) = dialogue_assets.get(&runner.dialogue_handle) else {
+            continue;
+        };
 
-                // Select the choice - this now also updates the state to ChoiceSelected
-                if let Err(err) = runner.select_choice(ev.choice_index) {
-                    error!("Error selecting choice: {}", err);
-                }
+        let old_node_id = runner.current_node_id;
+        match runner.submit_input(dialogue, &ev.value) {
+            Ok(()) => notify_advanced(
+                ev.entity,
+                &runner,
+                dialogue,
+                old_node_id,
+                &mut notifications,
+            ),
+            Err(error @ DialogueError::InvalidInput { .. }) => {
+                debug!("Input rejected on {:?}: {}", ev.entity, error);
+                notifications.error_occurred(DialogueErrorOccurred {
+                    entity: ev.entity,
+                    error,
+                });
+            }
+            Err(err) => {
+                handle_runner_error(ev.entity, &mut runner, dialogue, err, &mut notifications)
+            }
+        }
+    }
+}
 
-                // Send choice made event
-                dialogue_choice_events.send(crate::events::DialogueChoiceMade {
+/// System that moves dialogues to the nodes requested by [`JumpDialogue`] events.
+///
+/// The node the dialogue enters is notified with a [`DialogueNodeActivated`] event,
+/// or a [`DialogueEnded`] event if entering it finishes the dialogue. A dialogue that
+/// wasn't running is started, so a [`DialogueStarted`] event is sent first unless the
+/// jump finished it right away. A node
+/// that doesn't exist is reported as a [`DialogueErrorOccurred`] and leaves the
+/// runner untouched. Other errors are handled according to the runner's
+/// [`DialogueErrorPolicy`].
+///
+/// # System Parameters
+///
+/// * `dialogue_assets` - Assets resource containing loaded dialogue assets
+/// * `jump_events` - EventReader for JumpDialogue events
+/// * `notifications` - Sends dialogue notifications as events and triggers
+/// * `runner_query` - Query for DialogueRunner components
+pub fn handle_jump_events(
+    dialogue_assets: Res<Assets<DialogueAsset>>,
+    mut jump_events: EventReader<JumpDialogue>,
+    mut notifications: DialogueNotifications,
+    mut runner_query: Query<&mut DialogueRunner>,
+) {
+    for ev in jump_events.read() {
+        let Ok(mut runner) = runner_query.get_mut(ev.entity) else {
+            continue;
+        };
+        let Some(dialogue) = dialogue_assets.get(&runner.dialogue_handle) else {
+            continue;
+        };
+
+        let was_running = runner.is_running();
+        match runner.jump_to(dialogue, ev.node_id) {
+            Ok(()) => {
+                // Passed-through targets leave the runner on another node than the target
+                let started = !was_running && !runner.is_finished();
+                if let (Some(node_id), true) = (runner.current_node_id, started) {
+                    notifications.started(DialogueStarted {
+                        entity: ev.entity,
+                        start_node_id: node_id,
+                        entry_point: None,
+                    });
+                }
+                // Always notify, even when jumping to the current node
+                notify_advanced(ev.entity, &runner, dialogue, None, &mut notifications);
+            }
+            Err(error @ DialogueError::NodeNotFound(_)) => {
+                warn!("Dialogue jump failed on {:?}: {}", ev.entity, error);
+                notifications.error_occurred(DialogueErrorOccurred {
                     entity: ev.entity,
-                    node_id,
-                    choice_index: ev.choice_index,
+                    error,
                 });
             }
+            Err(err) => {
+                handle_runner_error(ev.entity, &mut runner, dialogue, err, &mut notifications)
+            }
+        }
+    }
+}
+
+/// System that checks dialogue speakers against the character database.
+///
+/// Whenever a dialogue asset or the character database referenced by
+/// [`DialogueCharacters`] is loaded or modified, every affected dialogue is
+/// validated and a warning is logged for speakers missing from the database.
+///
+/// The system does nothing if the `DialogueCharacters` resource is absent.
+///
+/// # System Parameters
+///
+/// * `characters` - Optional resource pointing at the active character database
+/// * `character_databases` - Assets resource containing loaded character databases
+/// * `dialogue_assets` - Assets resource containing loaded dialogue assets
+/// * `character_events` - EventReader for CharacterDatabase asset events
+/// * `dialogue_events` - EventReader for DialogueAsset asset events
+pub fn validate_dialogue_speakers(
+    characters: Option<Res<DialogueCharacters>>,
+    character_databases: Res<Assets<CharacterDatabase>>,
+    dialogue_assets: Res<Assets<DialogueAsset>>,
+    mut character_events: EventReader<AssetEvent<CharacterDatabase>>,
+    mut dialogue_events: EventReader<AssetEvent<DialogueAsset>>,
+) {
+    let Some(characters) = characters else {
+        character_events.clear();
+        dialogue_events.clear();
+        return;
+    };
+
+    // A changed database invalidates every dialogue, otherwise only changed dialogues are checked
+    let database_changed = character_events.read().any(|ev| match ev {
+        AssetEvent::Added { id } | AssetEvent::Modified { id } => *id == characters.0.id(),
+        _ => false,
+    });
+
+    let changed_dialogues: Vec<_> = dialogue_events
+        .read()
+        .filter_map(|ev| match ev {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+
+    let Some(database) = character_databases.get(&characters.0) else {
+        return;
+    };
+
+    for (id, dialogue) in dialogue_assets.iter() {
+        if !database_changed && !changed_dialogues.contains(&id) {
+            continue;
+        }
+
+        if let Err(err) = database.validate_speakers(&dialogue.graph) {
+            warn!(
+                "Dialogue {:?} ({:?}): {}",
+                dialogue.name.as_deref().unwrap_or("unnamed"),
+                id,
+                err
+            );
+        }
+    }
+}
+
+/// System that keeps running dialogues on track when their asset is hot reloaded.
+///
+/// For every modified dialogue asset, each runner of that dialogue is remapped onto
+/// the new graph with [`DialogueRunner::reload`]. Runners whose node was removed fall
+/// back to the start node, and a [`DialogueReloadWarning`] is sent for them.
+///
+/// # System Parameters
+///
+/// * `dialogue_assets` - Assets resource containing loaded dialogue assets
+/// * `asset_events` - EventReader for DialogueAsset asset events
+/// * `reload_warning_events` - EventWriter for DialogueReloadWarning events
+/// * `notifications` - Sends dialogue notifications as events and triggers
+/// * `runner_query` - Query for DialogueRunner components
+pub fn reload_dialogue_runners(
+    dialogue_assets: Res<Assets<DialogueAsset>>,
+    mut asset_events: EventReader<AssetEvent<DialogueAsset>>,
+    mut reload_warning_events: EventWriter<DialogueReloadWarning>,
+    mut notifications: DialogueNotifications,
+    mut runner_query: Query<(Entity, &mut DialogueRunner)>,
+) {
+    for ev in asset_events.read() {
+        let AssetEvent::Modified { id } = ev else {
+            continue;
+        };
+        let Some(dialogue) = dialogue_assets.get(*id) else {
+            continue;
+        };
+
+        for (entity, mut runner) in runner_query.iter_mut() {
+            if runner.dialogue_handle.id() != *id {
+                continue;
+            }
+
+            let Some(missing_node_id) = runner.reload(dialogue) else {
+                continue;
+            };
+
+            let fallback_node_id = runner
+                .current_node_id
+                .filter(|_| !matches!(runner.state, DialogueState::Error(_)));
+            warn!(
+                "Dialogue node {:?} was removed by a reload, {:?} fell back to {:?}",
+                missing_node_id, entity, fallback_node_id
+            );
+
+            reload_warning_events.send(DialogueReloadWarning {
+                entity,
+                missing_node_id,
+                fallback_node_id,
+            });
+            if let Some(event) = fallback_node_id
+                .and_then(|_| DialogueNodeActivated::from_runner(entity, dialogue, &runner))
+            {
+                notifications.node_activated(event);
+            }
+        }
+    }
+}
+
+/// Observer running the registered hooks of the custom nodes entered by runners.
+///
+/// The node type is looked up in the [`DialogueNodeRegistry`] and created from the node
+/// data. Its `on_enter` hook is queued as a command, and an `AdvanceDialogue` event is
+/// sent if its advance policy is [`CustomNodeAdvance::Immediate`]. Unknown node types
+/// and invalid data are handled as runner errors.
+pub fn enter_custom_node(
+    trigger: Trigger<DialogueNodeActivated>,
+    mut commands: Commands,
+    registry: Res<DialogueNodeRegistry>,
+    dialogue_assets: Res<Assets<DialogueAsset>>,
+    mut advance_events: EventWriter<AdvanceDialogue>,
+    mut notifications: DialogueNotifications,
+    mut runner_query: Query<&mut DialogueRunner>,
+) {
+    let event = trigger.event();
+    if event.kind != DialogueNodeKind::Custom {
+        return;
+    }
+
+    let entity = event.entity;
+    let Ok(mut runner) = runner_query.get_mut(entity) else {
+        return;
+    };
+    // The runner may have moved on before the observer ran
+    if runner.current_node_id != Some(event.node_id) {
+        return;
+    }
+    let Some(dialogue) = dialogue_assets.get(&runner.dialogue_handle) else {
+        return;
+    };
+    let Some(DialogueNode::Custom {
+        node_type, data, ..
+    }) = dialogue.graph.get_node(event.node_id)
+    else {
+        return;
+    };
+
+    match registry.create(event.node_id, node_type, data) {
+        Ok(node) => {
+            if node.advance_policy() == CustomNodeAdvance::Immediate {
+                advance_events.send(AdvanceDialogue { entity });
+            }
+            commands.queue(move |world: &mut World| node.on_enter(entity, world));
         }
+        Err(err) => handle_runner_error(entity, &mut runner, dialogue, err, &mut notifications),
+    }
+}
+
+/// Observer forwarding triggered [`StartDialogue`] events to the buffered event queue.
+///
+/// When the trigger targets an entity, that entity runs the dialogue.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use funkus_dialogue_core::StartDialogue;
+///
+/// fn talk_to(npc: Entity, mut commands: Commands, asset_server: Res<AssetServer>) {
+///     commands.trigger_targets(
+///         StartDialogue {
+///             entity: npc,
+///             dialogue_handle: asset_server.load("dialogues/example.dialogue.json"),
+///             entry_point: None,
+///         },
+///         npc,
+///     );
+/// }
+/// ```
+pub fn forward_start_dialogue_trigger(
+    trigger: Trigger<StartDialogue>,
+    mut start_events: EventWriter<StartDialogue>,
+) {
+    let mut event = trigger.event().clone();
+    if trigger.entity() != Entity::PLACEHOLDER {
+        event.entity = trigger.entity();
+    }
+    start_events.send(event);
+}
+
+/// Observer forwarding triggered [`AdvanceDialogue`] events to the buffered event queue.
+///
+/// When the trigger targets an entity, that entity's dialogue is advanced.
+pub fn forward_advance_dialogue_trigger(
+    trigger: Trigger<AdvanceDialogue>,
+    mut advance_events: EventWriter<AdvanceDialogue>,
+) {
+    let mut event = trigger.event().clone();
+    if trigger.entity() != Entity::PLACEHOLDER {
+        event.entity = trigger.entity();
     }
+    advance_events.send(event);
 }
 
 /// Set up the dialogue systems.
@@ -240,6 +793,11 @@ pub fn handle_dialogue_events(
 /// This function registers all dialogue-related systems with the Bevy app,
 /// configuring them with the appropriate system set for scheduling.
 ///
+/// The systems run one after the other, so events sent in the same frame are
+/// handled in a fixed order: reloaded assets are remapped first, then dialogues
+/// are started, stopped and advanced, inputs are submitted and jumps are made,
+/// and finally the timers of the runners are updated.
+///
 /// # Parameters
 ///
 /// * `app` - The Bevy App to configure
@@ -258,8 +816,286 @@ pub fn handle_dialogue_events(
 /// }
 /// ```
 pub fn setup_dialogue_systems(app: &mut App) {
-    app.configure_sets(Update, DialogueSystemSet).add_systems(
-        Update,
-        (update_dialogue_runners, handle_dialogue_events).in_set(DialogueSystemSet),
-    );
+    app.init_resource::<PendingDialogueStarts>()
+        .init_resource::<DialogueNodeRegistry>()
+        .add_observer(enter_custom_node)
+        .add_observer(forward_start_dialogue_trigger)
+        .add_observer(forward_advance_dialogue_trigger)
+        .configure_sets(Update, DialogueSystemSet)
+        .add_systems(
+            Update,
+            (
+                reload_dialogue_runners,
+                handle_dialogue_events,
+                handle_input_events,
+                handle_jump_events,
+                update_dialogue_runners,
+                validate_dialogue_speakers,
+            )
+                .chain()
+                .in_set(DialogueSystemSet),
+        );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{DialogueStartFailed, SelectDialogueChoice};
+    use crate::graph::{ConnectionData, DialogueGraph};
+    use crate::DialoguePlugin;
+
+    /// Creates a headless app running the dialogue systems.
+    fn dialogue_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default(), DialoguePlugin));
+        app
+    }
+
+    /// Adds a dialogue asset with the given graph.
+    fn add_dialogue(app: &mut App, graph: DialogueGraph) -> Handle<DialogueAsset> {
+        app.world_mut()
+            .resource_mut::<Assets<DialogueAsset>>()
+            .add(DialogueAsset::new(graph))
+    }
+
+    /// Takes the events of a type sent so far.
+    fn drain_events<E: Event>(app: &mut App) -> Vec<E> {
+        app.world_mut()
+            .resource_mut::<Events<E>>()
+            .drain()
+            .collect()
+    }
+
+    /// Creates a choice node leading to two text nodes.
+    fn choice_graph() -> DialogueGraph {
+        let mut graph = DialogueGraph::new(NodeId(1))
+            .with_node(DialogueNode::choice(NodeId(1)))
+            .with_node(DialogueNode::text(NodeId(2), "North"))
+            .with_node(DialogueNode::text(NodeId(3), "South"));
+        graph
+            .connect(NodeId(1), NodeId(2), ConnectionData::new(None))
+            .unwrap();
+        graph
+            .connect(NodeId(1), NodeId(3), ConnectionData::new(None))
+            .unwrap();
+        graph
+    }
+
+    /// Spawns an entity and starts a dialogue with the given graph on it.
+    fn start_dialogue(app: &mut App, graph: DialogueGraph) -> Entity {
+        let dialogue_handle = add_dialogue(app, graph);
+        let entity = app.world_mut().spawn_empty().id();
+        app.world_mut().send_event(StartDialogue {
+            entity,
+            dialogue_handle,
+            entry_point: None,
+        });
+        app.update();
+        entity
+    }
+
+    #[test]
+    fn test_start_twice_in_same_frame() {
+        let mut app = dialogue_app();
+        let first = add_dialogue(&mut app, choice_graph());
+        let second = add_dialogue(
+            &mut app,
+            DialogueGraph::new(NodeId(5)).with_node(DialogueNode::text(NodeId(5), "Hi")),
+        );
+        let entity = app.world_mut().spawn_empty().id();
+        for dialogue_handle in [first, second.clone()] {
+            app.world_mut().send_event(StartDialogue {
+                entity,
+                dialogue_handle,
+                entry_point: None,
+            });
+        }
+        app.update();
+
+        // The latest request wins, and only one runner is started
+        let started = drain_events::<DialogueStarted>(&mut app);
+        assert_eq!(started.len(), 1);
+        assert_eq!(started[0].start_node_id, NodeId(5));
+        let runner = app.world().get::<DialogueRunner>(entity).unwrap();
+        assert_eq!(runner.dialogue_handle, second);
+        assert_eq!(runner.current_node_id, Some(NodeId(5)));
+    }
+
+    #[test]
+    fn test_start_with_missing_start_node_fails() {
+        let mut app = dialogue_app();
+        let graph = DialogueGraph::new(NodeId(9)).with_node(DialogueNode::text(NodeId(1), "Hello"));
+        let entity = start_dialogue(&mut app, graph);
+
+        let failed = drain_events::<DialogueStartFailed>(&mut app);
+        assert!(matches!(
+            failed.as_slice(),
+            [DialogueStartFailed {
+                error: DialogueError::NodeNotFound(NodeId(9)),
+                ..
+            }]
+        ));
+        assert!(drain_events::<DialogueStarted>(&mut app).is_empty());
+        assert!(app.world().get::<DialogueRunner>(entity).is_none());
+    }
+
+    #[test]
+    fn test_start_with_handle_not_loading_fails() {
+        let mut app = dialogue_app();
+        let entity = app.world_mut().spawn_empty().id();
+        app.world_mut().send_event(StartDialogue {
+            entity,
+            dialogue_handle: Handle::default(),
+            entry_point: None,
+        });
+        app.update();
+
+        let failed = drain_events::<DialogueStartFailed>(&mut app);
+        assert!(matches!(
+            failed.as_slice(),
+            [DialogueStartFailed {
+                error: DialogueError::AssetNotLoaded,
+                ..
+            }]
+        ));
+        assert!(app.world().resource::<PendingDialogueStarts>().0.is_empty());
+    }
+
+    #[test]
+    fn test_start_and_advance_triggers_are_forwarded() {
+        let mut app = dialogue_app();
+        let mut graph = DialogueGraph::new(NodeId(1))
+            .with_node(DialogueNode::text(NodeId(1), "Hello"))
+            .with_node(DialogueNode::text(NodeId(2), "Bye"));
+        graph
+            .connect(NodeId(1), NodeId(2), ConnectionData::new(None))
+            .unwrap();
+        let dialogue_handle = add_dialogue(&mut app, graph);
+        let targeted = app.world_mut().spawn_empty().id();
+        let untargeted = app.world_mut().spawn_empty().id();
+        let progress = |app: &App, entity| {
+            let runner = app.world().get::<DialogueRunner>(entity).unwrap();
+            (runner.current_node_id, runner.is_finished())
+        };
+
+        // Targeted triggers act on their target
+        app.world_mut().trigger_targets(
+            StartDialogue {
+                entity: Entity::PLACEHOLDER,
+                dialogue_handle: dialogue_handle.clone(),
+                entry_point: None,
+            },
+            targeted,
+        );
+        app.update();
+        assert_eq!(progress(&app, targeted), (Some(NodeId(1)), false));
+        app.world_mut().trigger_targets(
+            AdvanceDialogue {
+                entity: Entity::PLACEHOLDER,
+            },
+            targeted,
+        );
+        app.update();
+        assert_eq!(progress(&app, targeted), (Some(NodeId(2)), false));
+
+        // Triggers without a target act on the entity of the event
+        app.world_mut().trigger(StartDialogue {
+            entity: untargeted,
+            dialogue_handle,
+            entry_point: None,
+        });
+        app.update();
+        assert_eq!(progress(&app, untargeted), (Some(NodeId(1)), false));
+        app.world_mut()
+            .trigger(AdvanceDialogue { entity: untargeted });
+        app.update();
+        assert_eq!(progress(&app, untargeted), (Some(NodeId(2)), false));
+        assert_eq!(progress(&app, targeted), (Some(NodeId(2)), false));
+    }
+
+    #[test]
+    fn test_advance_and_jump_in_same_frame() {
+        let mut app = dialogue_app();
+        let mut graph = DialogueGraph::new(NodeId(1))
+            .with_node(DialogueNode::text(NodeId(1), "Hello"))
+            .with_node(DialogueNode::text(NodeId(2), "How are you?"))
+            .with_node(DialogueNode::text(NodeId(3), "Watch out!"));
+        graph
+            .connect(NodeId(1), NodeId(2), ConnectionData::new(None))
+            .unwrap();
+        let entity = start_dialogue(&mut app, graph);
+        drain_events::<DialogueNodeActivated>(&mut app);
+
+        // Jumps are made after advances, whatever order the events were sent in
+        app.world_mut().send_event(JumpDialogue {
+            entity,
+            node_id: NodeId(3),
+        });
+        app.world_mut().send_event(AdvanceDialogue { entity });
+        app.update();
+
+        let runner = app.world().get::<DialogueRunner>(entity).unwrap();
+        assert_eq!(runner.current_node_id, Some(NodeId(3)));
+        let activated: Vec<_> = drain_events::<DialogueNodeActivated>(&mut app)
+            .into_iter()
+            .map(|ev| ev.node_id)
+            .collect();
+        assert_eq!(activated, vec![NodeId(2), NodeId(3)]);
+    }
+
+    #[test]
+    fn test_select_and_advance_in_same_frame() {
+        let mut app = dialogue_app();
+        let entity = start_dialogue(&mut app, choice_graph());
+        drain_events::<DialogueNodeActivated>(&mut app);
+
+        app.world_mut().send_event(SelectDialogueChoice {
+            entity,
+            choice_index: 1,
+        });
+        app.world_mut().send_event(AdvanceDialogue { entity });
+        app.update();
+
+        let runner = app.world().get::<DialogueRunner>(entity).unwrap();
+        assert_eq!(runner.current_node_id, Some(NodeId(3)));
+        assert!(drain_events::<DialogueErrorOccurred>(&mut app).is_empty());
+        let activated: Vec<_> = drain_events::<DialogueNodeActivated>(&mut app)
+            .into_iter()
+            .map(|ev| ev.node_id)
+            .collect();
+        assert_eq!(activated, vec![NodeId(3)]);
+    }
+
+    #[test]
+    fn test_rejected_choice_keeps_timeout_running() {
+        let mut app = dialogue_app();
+        let mut graph = choice_graph();
+        graph
+            .update_node(
+                NodeId(1),
+                DialogueNode::choice(NodeId(1))
+                    .with_timeout(4.0, 1)
+                    .unwrap(),
+            )
+            .unwrap();
+        let entity = start_dialogue(&mut app, graph);
+
+        app.world_mut().send_event(SelectDialogueChoice {
+            entity,
+            choice_index: 5,
+        });
+        app.update();
+
+        let errors = drain_events::<DialogueErrorOccurred>(&mut app);
+        assert!(matches!(
+            errors.as_slice(),
+            [DialogueErrorOccurred {
+                error: DialogueError::InvalidChoiceIndex(5, 1),
+                ..
+            }]
+        ));
+        let runner = app.world().get::<DialogueRunner>(entity).unwrap();
+        assert_eq!(runner.state, DialogueState::WaitingForChoice);
+        assert!(!runner.auto_advance_timer.paused());
+    }
 }
@@ -1,9 +1,70 @@
 use bevy::prelude::*;
+use funkus_dialogue_core::{DialogueState, NodeId};
 
 /// Component for dialogue display container
 #[derive(Component)]
+#[require(DisplayedDialogue)]
 pub struct DialogueDisplay;
 
+/// Component remembering which node and state a dialogue UI currently shows.
+///
+/// The display system only touches the UI when the linked runner enters a node,
+/// including the node it is already on, or changes state. Reset it to
+/// `DisplayedDialogue::default()` to force a refresh.
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+pub struct DisplayedDialogue {
+    /// Node, number of nodes entered by the runner and state last rendered, or None if
+    /// the UI hasn't been rendered yet
+    pub rendered: Option<(Option<NodeId>, usize, DialogueState)>,
+}
+
+/// Component linking a dialogue UI root to the runner entity it displays
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DialogueUiLink(pub Entity);
+
+/// How the dialogue of a runner is presented.
+///
+/// Add this component to a `DialogueRunner` entity to choose the UI spawned when
+/// its dialogue starts. Runners without it use the dialogue box.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DialoguePresentation {
+    /// Fixed dialogue box laid out by the `DialogueUiStyle`
+    #[default]
+    Box,
+    /// Speech bubble following the speaking entity in world space
+    Bubble,
+}
+
+/// Component mapping a world entity to a speaker ID.
+///
+/// Speech bubbles are anchored above the entity whose speaker ID matches the
+/// speaker of the current dialogue node. If no entity matches, the bubble is
+/// anchored above the runner entity itself.
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+pub struct DialogueSpeaker {
+    /// Speaker ID, as used in the `speaker` field of dialogue nodes
+    pub id: String,
+}
+
+impl DialogueSpeaker {
+    /// Creates a new speaker component with the given ID.
+    pub fn new(id: impl Into<String>) -> Self {
+        Self { id: id.into() }
+    }
+}
+
+/// Component for speech bubble containers
+#[derive(Component)]
+pub struct DialogueBubble;
+
+/// Component for the portrait of the current speaker.
+///
+/// Shows the portrait of the current node, or the default portrait of its speaker in
+/// the character database, loaded as an image asset path. The portrait is hidden
+/// when there is neither.
+#[derive(Component)]
+pub struct DialoguePortrait;
+
 /// Component for speaker text
 #[derive(Component)]
 pub struct SpeakerText;
@@ -20,6 +81,20 @@ pub struct ChoicesContainer;
 #[derive(Component)]
 pub struct ChoiceText(pub usize);
 
+/// Component for the countdown of timed choices
+#[derive(Component)]
+pub struct ChoiceCountdownText;
+
+/// Component for the text field of input nodes.
+///
+/// The field collects the characters typed while the linked runner waits for an
+/// input, and submits them when Enter is pressed.
+#[derive(Component, Debug, Clone, Default)]
+pub struct DialogueInputField {
+    /// Text typed so far
+    pub value: String,
+}
+
 /// Component for loading text indicator
 #[derive(Component)]
 pub struct LoadingText;
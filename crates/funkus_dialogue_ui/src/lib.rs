@@ -1,21 +1,45 @@
 //! # Funkus Dialogue UI
 //!
 //! UI components for displaying dialogues created with the funkus_dialogue system.
+//!
+//! Each dialogue UI is linked to a single `DialogueRunner` through [`DialogueUiLink`], so
+//! several conversations can be displayed at the same time. The plugin spawns a UI when
+//! a dialogue starts and despawns it when the dialogue ends.
+//!
+//! Runners are presented in a fixed dialogue box by default. Adding
+//! [`DialoguePresentation::Bubble`] to a runner shows its lines in a speech bubble
+//! that follows the speaking entity, identified by its [`DialogueSpeaker`] component.
 
 use bevy::prelude::*;
+use funkus_dialogue_core::DialogueSystemSet;
 
 // Components specific to dialogue UI
 mod components;
+mod style;
 mod systems;
 
 pub use components::*;
+pub use style::*;
 
 /// Plugin for dialogue UI functionality
 pub struct DialogueUIPlugin;
 
 impl Plugin for DialogueUIPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, systems::display_dialogue);
+        app.init_resource::<DialogueUiStyle>().add_systems(
+            Update,
+            (
+                systems::spawn_ui_on_dialogue_start,
+                systems::despawn_ui_on_dialogue_end,
+                systems::refresh_ui_on_dialogue_reload,
+                systems::display_dialogue,
+                systems::display_choice_countdown,
+                systems::edit_dialogue_input,
+                systems::position_dialogue_bubbles,
+            )
+                .chain()
+                .after(DialogueSystemSet),
+        );
     }
 }
 
@@ -26,46 +50,156 @@ pub struct DialogueUIBundle {
 }
 
 /// Function to spawn a dialogue UI
-pub fn spawn_dialogue_ui(commands: &mut Commands) -> Entity {
+///
+/// The UI displays the dialogue of the given runner entity, using the layout, fonts
+/// and colors of the given [`DialogueUiStyle`]. `DialogueUIPlugin` calls this
+/// automatically when a dialogue starts, so this is only needed to create a UI
+/// ahead of time.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use funkus_dialogue_core::DialogueRunner;
+/// use funkus_dialogue_ui::{spawn_dialogue_ui, DialogueUiStyle};
+///
+/// fn setup(mut commands: Commands, style: Res<DialogueUiStyle>) {
+///     let runner = commands.spawn(DialogueRunner::default()).id();
+///     spawn_dialogue_ui(&mut commands, &style, runner);
+/// }
+/// ```
+pub fn spawn_dialogue_ui(
+    commands: &mut Commands,
+    style: &DialogueUiStyle,
+    runner: Entity,
+) -> Entity {
+    let mut root = commands.spawn((style.box_node(), DialogueDisplay, DialogueUiLink(runner)));
+    style.insert_background(&mut root);
+
+    root.with_children(|parent| {
+        // Speaker portrait, hidden until a node has one
+        parent.spawn((
+            ImageNode::default(),
+            Node {
+                display: Display::None,
+                width: Val::Px(style.portrait_size),
+                height: Val::Px(style.portrait_size),
+                margin: UiRect::right(Val::Px(style.text_spacing)),
+                ..default()
+            },
+            DialoguePortrait,
+        ));
+
+        // Speaker name
+        parent.spawn((
+            Text::new(""),
+            style.speaker_font.clone(),
+            TextColor(style.speaker_color),
+            SpeakerText,
+        ));
+
+        // Dialogue text
+        parent.spawn((
+            Text::new(""),
+            style.text_font.clone(),
+            TextColor(style.text_color),
+            Node {
+                margin: UiRect::top(Val::Px(style.text_spacing)),
+                ..default()
+            },
+            DialogueText,
+        ));
+
+        // Choices container
+        parent.spawn((
+            Node {
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                margin: UiRect::top(Val::Px(style.choices_spacing)),
+                ..default()
+            },
+            ChoicesContainer,
+        ));
+
+        // Countdown of timed choices
+        parent.spawn((
+            Text::new(""),
+            style.choice_font.clone(),
+            TextColor(style.text_color),
+            ChoiceCountdownText,
+        ));
+
+        // Text field of input nodes
+        parent.spawn((
+            Text::new(""),
+            style.choice_font.clone(),
+            TextColor(style.selected_choice_color),
+            DialogueInputField::default(),
+        ));
+    })
+    .id()
+}
+
+/// Function to spawn a speech bubble UI
+///
+/// The bubble displays the dialogue of the given runner entity and is positioned
+/// above the current speaker every frame. `DialogueUIPlugin` calls this automatically
+/// when a runner with [`DialoguePresentation::Bubble`] starts a dialogue.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use funkus_dialogue_core::DialogueRunner;
+/// use funkus_dialogue_ui::{spawn_dialogue_bubble, DialogueSpeaker, DialogueUiStyle};
+///
+/// fn setup(mut commands: Commands, style: Res<DialogueUiStyle>) {
+///     let npc = commands
+///         .spawn((
+///             Transform::default(),
+///             DialogueSpeaker::new("guide"),
+///             DialogueRunner::default(),
+///         ))
+///         .id();
+///     spawn_dialogue_bubble(&mut commands, &style, npc);
+/// }
+/// ```
+pub fn spawn_dialogue_bubble(
+    commands: &mut Commands,
+    style: &DialogueUiStyle,
+    runner: Entity,
+) -> Entity {
     commands
         .spawn((
             Node {
                 position_type: PositionType::Absolute,
-                bottom: Val::Px(60.0),
-                left: Val::Px(100.0),
-                right: Val::Px(100.0),
-                height: Val::Px(200.0),
-                padding: UiRect::all(Val::Px(10.0)),
-                border: UiRect::all(Val::Px(2.0)),
+                flex_direction: FlexDirection::Column,
+                max_width: Val::Px(style.bubble.max_width),
+                padding: style.bubble.padding,
                 ..default()
             },
-            BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.8)),
+            BackgroundColor(style.bubble.background),
+            BorderRadius::all(style.bubble.corner_radius),
+            // Hidden until the first time the bubble is positioned
+            Visibility::Hidden,
             DialogueDisplay,
+            DialogueBubble,
+            DialogueUiLink(runner),
         ))
         .with_children(|parent| {
             // Speaker name
             parent.spawn((
                 Text::new(""),
-                TextFont {
-                    font_size: 20.0,
-                    ..default()
-                },
-                TextColor(Color::WHITE),
+                style.speaker_font.clone(),
+                TextColor(style.speaker_color),
                 SpeakerText,
             ));
 
             // Dialogue text
             parent.spawn((
                 Text::new(""),
-                TextFont {
-                    font_size: 18.0,
-                    ..default()
-                },
-                TextColor(Color::WHITE),
-                Node {
-                    margin: UiRect::top(Val::Px(10.0)),
-                    ..default()
-                },
+                style.text_font.clone(),
+                TextColor(style.text_color),
                 DialogueText,
             ));
 
@@ -74,11 +208,27 @@ pub fn spawn_dialogue_ui(commands: &mut Commands) -> Entity {
                 Node {
                     display: Display::Flex,
                     flex_direction: FlexDirection::Column,
-                    margin: UiRect::top(Val::Px(20.0)),
+                    margin: UiRect::top(Val::Px(style.text_spacing)),
                     ..default()
                 },
                 ChoicesContainer,
             ));
+
+            // Countdown of timed choices
+            parent.spawn((
+                Text::new(""),
+                style.choice_font.clone(),
+                TextColor(style.text_color),
+                ChoiceCountdownText,
+            ));
+
+            // Text field of input nodes
+            parent.spawn((
+                Text::new(""),
+                style.choice_font.clone(),
+                TextColor(style.selected_choice_color),
+                DialogueInputField::default(),
+            ));
         })
         .id()
 }
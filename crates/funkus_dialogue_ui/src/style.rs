@@ -0,0 +1,252 @@
+//! # Dialogue UI styling.
+//!
+//! This module defines the [`DialogueUiStyle`] resource that controls the layout
+//! and look of the default dialogue box.
+
+use bevy::prelude::*;
+use bevy::ui::widget::NodeImageMode;
+
+/// Where the dialogue box is anchored on screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum DialogueBoxAnchor {
+    /// Box sits at the bottom of the screen
+    #[default]
+    Bottom,
+    /// Box sits at the top of the screen
+    Top,
+    /// Box is centered vertically
+    Center,
+}
+
+/// Background of the dialogue box.
+#[derive(Debug, Clone)]
+pub enum DialogueBoxBackground {
+    /// Plain background color
+    Color(Color),
+    /// Nine-slice image, stretched to the size of the box
+    NineSlice {
+        /// The image to slice
+        image: Handle<Image>,
+        /// How the image is sliced
+        slicer: TextureSlicer,
+        /// Tint applied to the image
+        color: Color,
+    },
+}
+
+/// Layout and look of speech bubbles.
+#[derive(Debug, Clone)]
+pub struct DialogueBubbleStyle {
+    /// Maximum width of a bubble, in pixels; bubbles shrink to fit shorter text
+    pub max_width: f32,
+    /// World space offset from the speaker's position to the bubble anchor
+    pub world_offset: Vec3,
+    /// Minimum distance between a bubble and the screen edges, in pixels
+    pub screen_margin: f32,
+    /// Space between the bubble edges and its content
+    pub padding: UiRect,
+    /// Background color of the bubble
+    pub background: Color,
+    /// Corner radius of the bubble
+    pub corner_radius: Val,
+}
+
+impl Default for DialogueBubbleStyle {
+    fn default() -> Self {
+        Self {
+            max_width: 320.0,
+            world_offset: Vec3::new(0.0, 60.0, 0.0),
+            screen_margin: 8.0,
+            padding: UiRect::all(Val::Px(8.0)),
+            background: Color::srgba(0.1, 0.1, 0.1, 0.85),
+            corner_radius: Val::Px(8.0),
+        }
+    }
+}
+
+/// Resource describing the layout and look of the default dialogue UI.
+///
+/// Insert or modify this resource before the dialogue UI is spawned to change
+/// how the box looks. The defaults reproduce the original dialogue box.
+///
+/// The style can also be added as a component to a `DialogueRunner` entity, in
+/// which case it overrides the resource for that runner's UI only. This keeps
+/// several conversations on screen from overlapping.
+///
+/// # Fields
+///
+/// * `anchor` - Where the box is anchored on screen
+/// * `margin` - Distance between the box and the screen edges
+/// * `width` - Width of the box (`Val::Auto` stretches between the margins)
+/// * `height` - Height of the box
+/// * `padding` - Space between the box edges and its content
+/// * `background` - Background color or nine-slice image
+/// * `speaker_font` / `text_font` / `choice_font` - Fonts and font sizes
+/// * `speaker_color` / `text_color` - Default text colors
+/// * `choice_color` / `selected_choice_color` - Choice text colors
+/// * `selected_choice_marker` - Prefix shown in front of the selected choice
+/// * `portrait_size` - Width and height of the speaker portrait
+/// * `bubble` - Layout and look of speech bubbles
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use funkus_dialogue_ui::{DialogueBoxAnchor, DialogueUiStyle};
+///
+/// fn setup_style(mut style: ResMut<DialogueUiStyle>) {
+///     style.anchor = DialogueBoxAnchor::Top;
+///     style.text_font.font_size = 24.0;
+///     style.selected_choice_marker = "> ".to_string();
+/// }
+/// ```
+#[derive(Resource, Component, Debug, Clone)]
+pub struct DialogueUiStyle {
+    /// Where the box is anchored on screen
+    pub anchor: DialogueBoxAnchor,
+    /// Distance between the box and the screen edges
+    pub margin: UiRect,
+    /// Width of the box
+    pub width: Val,
+    /// Height of the box
+    pub height: Val,
+    /// Space between the box edges and its content
+    pub padding: UiRect,
+    /// Border width of the box
+    pub border: UiRect,
+    /// Background color or nine-slice image
+    pub background: DialogueBoxBackground,
+    /// Font used for the speaker name
+    pub speaker_font: TextFont,
+    /// Font used for the dialogue text
+    pub text_font: TextFont,
+    /// Font used for choices
+    pub choice_font: TextFont,
+    /// Default color of the speaker name
+    pub speaker_color: Color,
+    /// Color of the dialogue text
+    pub text_color: Color,
+    /// Color of unselected choices
+    pub choice_color: Color,
+    /// Color of the selected choice
+    pub selected_choice_color: Color,
+    /// Prefix shown in front of the selected choice
+    pub selected_choice_marker: String,
+    /// Space above the dialogue text, in pixels
+    pub text_spacing: f32,
+    /// Space above the choice list, in pixels
+    pub choices_spacing: f32,
+    /// Space between choices, in pixels
+    pub choice_spacing: f32,
+    /// Width and height of the speaker portrait, in pixels
+    pub portrait_size: f32,
+    /// Layout and look of speech bubbles
+    pub bubble: DialogueBubbleStyle,
+}
+
+impl Default for DialogueUiStyle {
+    fn default() -> Self {
+        Self {
+            anchor: DialogueBoxAnchor::Bottom,
+            margin: UiRect {
+                left: Val::Px(100.0),
+                right: Val::Px(100.0),
+                top: Val::Px(60.0),
+                bottom: Val::Px(60.0),
+            },
+            width: Val::Auto,
+            height: Val::Px(200.0),
+            padding: UiRect::all(Val::Px(10.0)),
+            border: UiRect::all(Val::Px(2.0)),
+            background: DialogueBoxBackground::Color(Color::srgba(0.1, 0.1, 0.1, 0.8)),
+            speaker_font: TextFont {
+                font_size: 20.0,
+                ..default()
+            },
+            text_font: TextFont {
+                font_size: 18.0,
+                ..default()
+            },
+            choice_font: TextFont {
+                font_size: 16.0,
+                ..default()
+            },
+            speaker_color: Color::WHITE,
+            text_color: Color::WHITE,
+            choice_color: Color::srgb(0.8, 0.8, 1.0),
+            selected_choice_color: Color::srgb(1.0, 1.0, 0.5),
+            selected_choice_marker: "▶ ".to_string(),
+            text_spacing: 10.0,
+            choices_spacing: 20.0,
+            choice_spacing: 5.0,
+            portrait_size: 96.0,
+            bubble: DialogueBubbleStyle::default(),
+        }
+    }
+}
+
+impl DialogueUiStyle {
+    /// Builds the layout node for the dialogue box.
+    pub fn box_node(&self) -> Node {
+        let mut node = Node {
+            position_type: PositionType::Absolute,
+            left: self.margin.left,
+            right: self.margin.right,
+            width: self.width,
+            height: self.height,
+            padding: self.padding,
+            border: self.border,
+            ..default()
+        };
+
+        match self.anchor {
+            DialogueBoxAnchor::Bottom => node.bottom = self.margin.bottom,
+            DialogueBoxAnchor::Top => node.top = self.margin.top,
+            DialogueBoxAnchor::Center => {
+                node.top = self.margin.top;
+                node.bottom = self.margin.bottom;
+                node.margin = UiRect::vertical(Val::Auto);
+            }
+        }
+
+        node
+    }
+
+    /// Adds the background components to the dialogue box entity.
+    pub fn insert_background(&self, entity: &mut EntityCommands) {
+        match &self.background {
+            DialogueBoxBackground::Color(color) => {
+                entity.insert(BackgroundColor(*color));
+            }
+            DialogueBoxBackground::NineSlice {
+                image,
+                slicer,
+                color,
+            } => {
+                entity.insert(
+                    ImageNode::new(image.clone())
+                        .with_color(*color)
+                        .with_mode(NodeImageMode::Sliced(slicer.clone())),
+                );
+            }
+        }
+    }
+
+    /// Gets the text and color to display for a choice.
+    ///
+    /// # Parameters
+    ///
+    /// * `index` - Index of the choice
+    /// * `label` - Text of the choice
+    /// * `selected` - Whether the choice is currently selected
+    pub fn choice_display(&self, index: usize, label: &str, selected: bool) -> (String, Color) {
+        if selected {
+            (
+                format!("{}{}. {}", self.selected_choice_marker, index + 1, label),
+                self.selected_choice_color,
+            )
+        } else {
+            (format!("{}. {}", index + 1, label), self.choice_color)
+        }
+    }
+}
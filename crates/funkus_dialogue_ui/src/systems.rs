@@ -1,17 +1,210 @@
 /*
  * Early UI module - needs a lot of work, adapted from example.
  */
+use bevy::input::keyboard::{Key, KeyboardInput};
 use bevy::prelude::*;
-use funkus_dialogue_core::{DialogueAsset, DialogueNode, DialogueRunner, DialogueState};
+use funkus_dialogue_core::{
+    CharacterDatabase, DialogueAsset, DialogueCharacters, DialogueEnded, DialogueNode,
+    DialogueRunner, DialogueStarted, DialogueState, SubmitDialogueInput,
+};
 
 use crate::components::*;
+use crate::style::DialogueUiStyle;
+use crate::{spawn_dialogue_bubble, spawn_dialogue_ui};
+
+/// Content shown by a dialogue UI for the current node of a runner.
+struct DialogueView {
+    /// Raw speaker ID of the current node
+    speaker: Option<String>,
+    /// Portrait of the current node
+    portrait: Option<String>,
+    /// Text or prompt of the current node
+    text: String,
+    /// Labels of the available choices
+    choices: Vec<String>,
+    /// Index of the selected choice, if any
+    selected: Option<usize>,
+}
+
+impl DialogueView {
+    /// Builds the view for the current node of a runner.
+    ///
+    /// Returns None if the runner is inactive or its dialogue isn't loaded.
+    fn from_runner(
+        runner: &DialogueRunner,
+        dialogue_assets: &Assets<DialogueAsset>,
+    ) -> Option<Self> {
+        if runner.state == DialogueState::Inactive {
+            return None;
+        }
+
+        let dialogue = dialogue_assets.get(&runner.dialogue_handle)?;
+        let node_id = runner.current_node_id?;
+        let node = dialogue.graph.get_node(node_id)?;
+
+        let portrait = node.portrait().map(str::to_string);
+        let view = match node {
+            DialogueNode::Text { speaker, .. } => Self {
+                speaker: speaker.clone(),
+                portrait,
+                text: runner
+                    .current_text(dialogue)
+                    .unwrap_or_default()
+                    .to_string(),
+                choices: Vec::new(),
+                selected: None,
+            },
+            DialogueNode::Choice {
+                prompt, speaker, ..
+            } => Self {
+                speaker: speaker.clone(),
+                portrait,
+                text: prompt
+                    .clone()
+                    .unwrap_or_else(|| "Choose an option:".to_string()),
+                choices: runner
+                    .available_choices(dialogue, node_id)
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, (_, connection))| {
+                        connection
+                            .label
+                            .clone()
+                            .unwrap_or_else(|| format!("Choice {}", i + 1))
+                    })
+                    .collect(),
+                selected: match runner.state {
+                    DialogueState::ChoiceSelected(index) => Some(index),
+                    _ => None,
+                },
+            },
+            DialogueNode::Input {
+                prompt, speaker, ..
+            } => Self {
+                speaker: speaker.clone(),
+                portrait,
+                text: prompt
+                    .clone()
+                    .unwrap_or_else(|| "Enter a response:".to_string()),
+                choices: Vec::new(),
+                selected: None,
+            },
+            // Random and return nodes are passed through, end nodes finish the dialogue,
+            // wait nodes show nothing and custom nodes are handled by the game, all show
+            // an empty box
+            DialogueNode::Random { .. }
+            | DialogueNode::Return { .. }
+            | DialogueNode::End { .. }
+            | DialogueNode::Wait { .. }
+            | DialogueNode::Custom { .. } => Self {
+                speaker: None,
+                portrait: None,
+                text: String::new(),
+                choices: Vec::new(),
+                selected: None,
+            },
+        };
+
+        Some(view)
+    }
+}
+
+/// System that spawns a dialogue UI for every runner that starts a dialogue.
+///
+/// Runners that already have a linked UI are skipped. A [`DialogueUiStyle`] component
+/// on the runner entity overrides the global style resource, and a
+/// [`DialoguePresentation`] component selects between a box and a speech bubble.
+pub fn spawn_ui_on_dialogue_start(
+    mut commands: Commands,
+    style: Res<DialogueUiStyle>,
+    mut started_events: EventReader<DialogueStarted>,
+    runner_query: Query<
+        (Option<&DialogueUiStyle>, Option<&DialoguePresentation>),
+        With<DialogueRunner>,
+    >,
+    ui_query: Query<&DialogueUiLink>,
+) {
+    for ev in started_events.read() {
+        if ui_query.iter().any(|link| link.0 == ev.entity) {
+            continue;
+        }
+
+        let Ok((runner_style, presentation)) = runner_query.get(ev.entity) else {
+            continue;
+        };
+        let style = runner_style.unwrap_or(&style);
+
+        match presentation.copied().unwrap_or_default() {
+            DialoguePresentation::Box => spawn_dialogue_ui(&mut commands, style, ev.entity),
+            DialoguePresentation::Bubble => spawn_dialogue_bubble(&mut commands, style, ev.entity),
+        };
+    }
+}
+
+/// System that despawns the UI linked to a runner once its dialogue ends.
+pub fn despawn_ui_on_dialogue_end(
+    mut commands: Commands,
+    mut ended_events: EventReader<DialogueEnded>,
+    ui_query: Query<(Entity, &DialogueUiLink)>,
+) {
+    for ev in ended_events.read() {
+        for (ui_entity, link) in ui_query.iter() {
+            if link.0 == ev.entity {
+                commands.entity(ui_entity).despawn_recursive();
+            }
+        }
+    }
+}
+
+/// System that redraws dialogue UIs whose dialogue asset was modified.
+///
+/// Hot reloads can change the text of the current node without moving the runner,
+/// so the cached [`DisplayedDialogue`] of the affected UIs is reset.
+pub fn refresh_ui_on_dialogue_reload(
+    mut asset_events: EventReader<AssetEvent<DialogueAsset>>,
+    runner_query: Query<&DialogueRunner>,
+    mut ui_query: Query<(&DialogueUiLink, &mut DisplayedDialogue)>,
+) {
+    for ev in asset_events.read() {
+        let AssetEvent::Modified { id } = ev else {
+            continue;
+        };
+
+        for (link, mut displayed) in ui_query.iter_mut() {
+            let Ok(runner) = runner_query.get(link.0) else {
+                continue;
+            };
+            if runner.dialogue_handle.id() == *id {
+                *displayed = DisplayedDialogue::default();
+            }
+        }
+    }
+}
 
 /// System to display dialogue content.
+///
+/// Every dialogue UI root shows the dialogue of the runner it is linked to through
+/// [`DialogueUiLink`]. UIs whose runner no longer exists are despawned.
+///
+/// The UI is only updated when the runner enters a node or changes state. Entering
+/// the node the runner is already on, such as a hub it returns to, counts as a new
+/// node. Selecting a different choice recolors the existing choice entries instead of
+/// respawning them, so choice entities stay alive for hover states and animations.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn display_dialogue(
     mut commands: Commands,
+    asset_server: Res<AssetServer>,
     dialogue_assets: Res<Assets<DialogueAsset>>,
-    dialogue_query: Query<(&DialogueRunner, &Name)>,
-    mut speaker_query: Query<&mut Text, With<SpeakerText>>,
+    style: Res<DialogueUiStyle>,
+    characters: Option<Res<DialogueCharacters>>,
+    character_databases: Res<Assets<CharacterDatabase>>,
+    runner_query: Query<(Ref<DialogueRunner>, Option<&DialogueUiStyle>)>,
+    mut ui_query: Query<
+        (Entity, &DialogueUiLink, &Children, &mut DisplayedDialogue),
+        With<DialogueDisplay>,
+    >,
+    mut speaker_query: Query<(&mut Text, &mut TextColor), With<SpeakerText>>,
+    mut portrait_query: Query<(&mut ImageNode, &mut Node), With<DialoguePortrait>>,
     mut dialogue_query_text: Query<
         &mut Text,
         (
@@ -20,124 +213,732 @@ pub fn display_dialogue(
             Without<ChoiceText>,
         ),
     >,
-    choices_query: Query<Entity, With<ChoicesContainer>>,
+    choices_query: Query<Option<&Children>, With<ChoicesContainer>>,
+    mut choice_query: Query<
+        (&ChoiceText, &mut Text, &mut TextColor),
+        (Without<SpeakerText>, Without<DialogueText>),
+    >,
 ) {
-    // Find the first active dialogue
-    for (runner, _) in dialogue_query.iter() {
-        if runner.state == DialogueState::Inactive {
-            // Clear UI when dialogue is inactive
-            for mut speaker_text in speaker_query.iter_mut() {
-                *speaker_text = Text::new("");
+    let database = characters
+        .as_ref()
+        .and_then(|characters| character_databases.get(&characters.0));
+
+    for (ui_entity, link, children, mut displayed) in ui_query.iter_mut() {
+        let Ok((runner, runner_style)) = runner_query.get(link.0) else {
+            // The runner is gone, so is its UI
+            commands.entity(ui_entity).despawn_recursive();
+            continue;
+        };
+
+        // Nothing to do until the runner changes
+        if !runner.is_changed() && displayed.rendered.is_some() {
+            continue;
+        }
+
+        // The history length tells apart the visits of a node the runner re-enters
+        let key = (
+            runner.current_node_id,
+            runner.history.len(),
+            runner.state.clone(),
+        );
+        if displayed.rendered.as_ref() == Some(&key) {
+            continue;
+        }
+
+        // Inactive runners show an empty box, active runners wait for their dialogue to load
+        let view = if runner.state == DialogueState::Inactive {
+            None
+        } else {
+            match DialogueView::from_runner(&runner, &dialogue_assets) {
+                Some(view) => Some(view),
+                None => continue,
+            }
+        };
+
+        let node_entered = displayed
+            .rendered
+            .as_ref()
+            .is_none_or(|(node_id, entered, _)| (*node_id, *entered) != (key.0, key.1));
+        displayed.rendered = Some(key);
+        let style = runner_style.unwrap_or(&style);
+
+        for &child in children.iter() {
+            if let Ok(choice_children) = choices_query.get(child) {
+                let shown_choices = choice_children.map_or(0, |choices| choices.len());
+                let choices = view.as_ref().map_or(0, |view| view.choices.len());
+                if node_entered || shown_choices != choices {
+                    rebuild_choices(&mut commands, child, view.as_ref(), style);
+                } else if let (Some(view), Some(choice_children)) = (&view, choice_children) {
+                    // Same node, only the selection changed
+                    for &choice_entity in choice_children.iter() {
+                        let Ok((choice, mut text, mut color)) = choice_query.get_mut(choice_entity)
+                        else {
+                            continue;
+                        };
+                        let Some(label) = view.choices.get(choice.0) else {
+                            continue;
+                        };
+
+                        let (display_text, choice_color) =
+                            style.choice_display(choice.0, label, Some(choice.0) == view.selected);
+                        *text = Text::new(display_text);
+                        *color = TextColor(choice_color);
+                    }
+                }
             }
 
-            for mut dialogue_text in dialogue_query_text.iter_mut() {
-                *dialogue_text = Text::new("");
+            if !node_entered {
+                continue;
             }
 
-            for choices_entity in choices_query.iter() {
-                commands.entity(choices_entity).despawn_descendants();
+            if let Ok((mut speaker_text, mut color)) = speaker_query.get_mut(child) {
+                let (speaker_name, speaker_color) = resolve_speaker(
+                    view.as_ref().and_then(|view| view.speaker.as_deref()),
+                    database,
+                    style,
+                );
+                *speaker_text = Text::new(speaker_name);
+                *color = TextColor(speaker_color);
             }
 
-            continue;
+            if let Ok((mut image, mut portrait_node)) = portrait_query.get_mut(child) {
+                match resolve_portrait(view.as_ref(), database) {
+                    Some(portrait) => {
+                        image.image = asset_server.load(portrait.to_string());
+                        portrait_node.display = Display::Flex;
+                    }
+                    None => portrait_node.display = Display::None,
+                }
+            }
+
+            if let Ok(mut dialogue_text) = dialogue_query_text.get_mut(child) {
+                *dialogue_text = Text::new(
+                    view.as_ref()
+                        .map(|view| view.text.clone())
+                        .unwrap_or_default(),
+                );
+            }
         }
+    }
+}
+
+/// System that shows the time left on timed choices.
+///
+/// The countdown is rounded up to whole seconds and is empty when the runner
+/// isn't waiting on a timed choice.
+pub fn display_choice_countdown(
+    dialogue_assets: Res<Assets<DialogueAsset>>,
+    runner_query: Query<&DialogueRunner>,
+    ui_query: Query<(&DialogueUiLink, &Children), With<DialogueDisplay>>,
+    mut countdown_query: Query<&mut Text, With<ChoiceCountdownText>>,
+) {
+    for (link, children) in ui_query.iter() {
+        let time_left = runner_query.get(link.0).ok().and_then(|runner| {
+            let dialogue = dialogue_assets.get(&runner.dialogue_handle)?;
+            runner.choice_time_left(dialogue)
+        });
+        let countdown = time_left
+            .map(|seconds| format!("{}", seconds.ceil()))
+            .unwrap_or_default();
 
-        // Get dialogue asset
-        if let Some(dialogue) = dialogue_assets.get(&runner.dialogue_handle) {
-            if let Some(node_id) = runner.current_node_id {
-                if let Some(node) = dialogue.graph.get_node(node_id) {
-                    // Process based on node type
-                    match node {
-                        DialogueNode::Text { text, speaker, .. } => {
-                            // Update speaker
-                            for mut speaker_text in speaker_query.iter_mut() {
-                                if let Some(speaker_name) = speaker {
-                                    *speaker_text = Text::new(speaker_name.clone());
-                                } else {
-                                    *speaker_text = Text::new("");
-                                }
-                            }
-
-                            // Update dialogue text
-                            for mut dialogue_text in dialogue_query_text.iter_mut() {
-                                *dialogue_text = Text::new(text.clone());
-                            }
-
-                            // Clear choices
-                            for choices_entity in choices_query.iter() {
-                                commands.entity(choices_entity).despawn_descendants();
-                            }
-                        }
-                        DialogueNode::Choice {
-                            prompt, speaker, ..
-                        } => {
-                            // Update speaker
-                            for mut speaker_text in speaker_query.iter_mut() {
-                                if let Some(speaker_name) = speaker {
-                                    *speaker_text = Text::new(speaker_name.clone());
-                                } else {
-                                    *speaker_text = Text::new("");
-                                }
-                            }
-
-                            // Update dialogue text (prompt)
-                            for mut dialogue_text in dialogue_query_text.iter_mut() {
-                                if let Some(prompt_text) = prompt {
-                                    *dialogue_text = Text::new(prompt_text.clone());
-                                } else {
-                                    *dialogue_text = Text::new("Choose an option:");
-                                }
-                            }
-
-                            // Handle the ChoiceSelected state
-                            let selected_index = match runner.state {
-                                DialogueState::ChoiceSelected(index) => Some(index),
-                                _ => None,
-                            };
-
-                            // Get connections from the graph structure
-                            let connections = dialogue.graph.get_connected_nodes(node_id);
-
-                            for choices_entity in choices_query.iter() {
-                                commands.entity(choices_entity).despawn_descendants();
-
-                                // Add choice buttons
-                                for (i, (_, label)) in connections.iter().enumerate() {
-                                    let choice_text = label
-                                        .clone()
-                                        .unwrap_or_else(|| format!("Choice {}", i + 1));
-
-                                    let display_text = if Some(i) == selected_index {
-                                        // Highlight selected choice
-                                        format!("▶ {}. {}", i + 1, choice_text)
-                                    } else {
-                                        format!("{}. {}", i + 1, choice_text)
-                                    };
-
-                                    commands.entity(choices_entity).with_children(|parent| {
-                                        parent.spawn((
-                                            Text::new(display_text),
-                                            TextFont {
-                                                font_size: 16.0,
-                                                ..default()
-                                            },
-                                            TextColor(if Some(i) == selected_index {
-                                                Color::srgb(1.0, 1.0, 0.5) // Highlight selected choice
-                                            } else {
-                                                Color::srgb(0.8, 0.8, 1.0)
-                                            }),
-                                            Node {
-                                                margin: UiRect::bottom(Val::Px(5.0)),
-                                                ..default()
-                                            },
-                                            ChoiceText(i),
-                                        ));
-                                    });
-                                }
-                            }
-                        }
+        for &child in children.iter() {
+            if let Ok(mut text) = countdown_query.get_mut(child) {
+                // Avoid change detection when the countdown shows the same value
+                if text.0 != countdown {
+                    text.0 = countdown.clone();
+                }
+            }
+        }
+    }
+}
+
+/// System that lets the player type into the text field of input nodes.
+///
+/// While the linked runner waits for an input, typed characters are appended to the
+/// [`DialogueInputField`], Backspace removes the last one and Enter submits the text
+/// with a [`SubmitDialogueInput`] event. The field is cleared and hidden otherwise.
+pub fn edit_dialogue_input(
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut submit_events: EventWriter<SubmitDialogueInput>,
+    runner_query: Query<&DialogueRunner>,
+    ui_query: Query<(&DialogueUiLink, &Children), With<DialogueDisplay>>,
+    mut field_query: Query<(&mut DialogueInputField, &mut Text)>,
+) {
+    let pressed: Vec<Key> = keyboard_events
+        .read()
+        .filter(|ev| ev.state.is_pressed())
+        .map(|ev| ev.logical_key.clone())
+        .collect();
+
+    for (link, children) in ui_query.iter() {
+        let waiting = runner_query
+            .get(link.0)
+            .is_ok_and(|runner| runner.state == DialogueState::WaitingForInput);
+
+        for &child in children.iter() {
+            let Ok((mut field, mut text)) = field_query.get_mut(child) else {
+                continue;
+            };
+
+            if !waiting {
+                if !field.value.is_empty() || !text.0.is_empty() {
+                    field.value.clear();
+                    text.0.clear();
+                }
+                continue;
+            }
+
+            for key in &pressed {
+                match key {
+                    Key::Character(characters) => {
+                        field
+                            .value
+                            .extend(characters.chars().filter(|c| !c.is_control()));
+                    }
+                    Key::Space => field.value.push(' '),
+                    Key::Backspace => {
+                        field.value.pop();
                     }
+                    Key::Enter => {
+                        submit_events.send(SubmitDialogueInput {
+                            entity: link.0,
+                            value: std::mem::take(&mut field.value),
+                        });
+                    }
+                    _ => {}
                 }
             }
+
+            let shown = format!("> {}_", field.value);
+            if text.0 != shown {
+                text.0 = shown;
+            }
+        }
+    }
+}
+
+/// Replaces the choice entries of a choices container.
+fn rebuild_choices(
+    commands: &mut Commands,
+    choices_entity: Entity,
+    view: Option<&DialogueView>,
+    style: &DialogueUiStyle,
+) {
+    commands.entity(choices_entity).despawn_descendants();
+
+    let Some(view) = view else {
+        return;
+    };
+
+    // Add choice buttons
+    commands.entity(choices_entity).with_children(|parent| {
+        for (i, choice_text) in view.choices.iter().enumerate() {
+            let (display_text, color) =
+                style.choice_display(i, choice_text, Some(i) == view.selected);
+
+            parent.spawn((
+                Text::new(display_text),
+                style.choice_font.clone(),
+                TextColor(color),
+                Node {
+                    margin: UiRect::bottom(Val::Px(style.choice_spacing)),
+                    ..default()
+                },
+                ChoiceText(i),
+            ));
         }
+    });
+}
+
+/// System that keeps speech bubbles above their speakers.
+///
+/// Each bubble is anchored above the entity whose [`DialogueSpeaker`] matches the
+/// speaker of the runner's current node, falling back to the runner entity. The
+/// anchor is projected to screen space with the first active camera, and the bubble
+/// is clamped so it stays fully on screen. Bubbles without a visible anchor are hidden.
+#[allow(clippy::too_many_arguments)]
+pub fn position_dialogue_bubbles(
+    dialogue_assets: Res<Assets<DialogueAsset>>,
+    style: Res<DialogueUiStyle>,
+    runner_query: Query<(&DialogueRunner, Option<&DialogueUiStyle>)>,
+    speaker_query: Query<(&DialogueSpeaker, &GlobalTransform)>,
+    transform_query: Query<&GlobalTransform>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    mut bubble_query: Query<
+        (&DialogueUiLink, &mut Node, &mut Visibility, &ComputedNode),
+        With<DialogueBubble>,
+    >,
+) {
+    let Some((camera, camera_transform)) = camera_query.iter().find(|(camera, _)| camera.is_active)
+    else {
+        return;
+    };
+    let Some(viewport_size) = camera.logical_viewport_size() else {
+        return;
+    };
+
+    for (link, mut node, mut visibility, computed) in bubble_query.iter_mut() {
+        let Ok((runner, runner_style)) = runner_query.get(link.0) else {
+            continue;
+        };
+        let bubble_style = &runner_style.unwrap_or(&style).bubble;
+
+        // Find the entity speaking the current line
+        let speaker = dialogue_assets
+            .get(&runner.dialogue_handle)
+            .and_then(|dialogue| runner.current_node(dialogue))
+            .and_then(|node| node.speaker());
+        let anchor = speaker
+            .and_then(|speaker| {
+                speaker_query
+                    .iter()
+                    .find(|(dialogue_speaker, _)| dialogue_speaker.id == speaker)
+                    .map(|(_, transform)| transform)
+            })
+            .or_else(|| transform_query.get(link.0).ok());
+
+        let screen_position = anchor.and_then(|transform| {
+            camera
+                .world_to_viewport(
+                    camera_transform,
+                    transform.translation() + bubble_style.world_offset,
+                )
+                .ok()
+        });
+
+        let Some(screen_position) = screen_position else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+
+        // Center the bubble horizontally above the anchor, then keep it on screen
+        let size = computed.size() * computed.inverse_scale_factor();
+        let margin = bubble_style.screen_margin;
+        let max_left = (viewport_size.x - size.x - margin).max(margin);
+        let max_top = (viewport_size.y - size.y - margin).max(margin);
+
+        node.left = Val::Px((screen_position.x - size.x / 2.0).clamp(margin, max_left));
+        node.top = Val::Px((screen_position.y - size.y).clamp(margin, max_top));
+        *visibility = Visibility::Inherited;
+    }
+}
+
+/// Resolves the name and color to display for a speaker ID.
+///
+/// Speakers found in the character database use their display name and name color,
+/// any other speaker is shown verbatim in the style's speaker color.
+fn resolve_speaker(
+    speaker: Option<&str>,
+    database: Option<&CharacterDatabase>,
+    style: &DialogueUiStyle,
+) -> (String, Color) {
+    let Some(speaker) = speaker else {
+        return (String::new(), style.speaker_color);
+    };
+
+    match database.and_then(|database| database.get(speaker)) {
+        Some(character) => (
+            character.display_name.clone(),
+            character.color().unwrap_or(style.speaker_color),
+        ),
+        None => (speaker.to_string(), style.speaker_color),
+    }
+}
+
+/// Resolves the portrait to display for a view.
+///
+/// Nodes without a portrait fall back to the default portrait of their speaker in
+/// the character database.
+fn resolve_portrait<'a>(
+    view: Option<&'a DialogueView>,
+    database: Option<&'a CharacterDatabase>,
+) -> Option<&'a str> {
+    let view = view?;
+    view.portrait
+        .as_deref()
+        .or_else(|| database?.default_portrait(view.speaker.as_deref()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::DialogueBoxAnchor;
+    use crate::DialogueUIPlugin;
+    use bevy::ecs::system::RunSystemOnce;
+    use bevy::input::keyboard::{KeyCode, NativeKeyCode};
+    use bevy::input::{ButtonState, InputPlugin};
+    use bevy::render::camera::{camera_system, ManualTextureViews};
+    use funkus_dialogue_core::graph::TextVariantMode;
+    use funkus_dialogue_core::graph::{ConnectionData, DialogueGraph, NodeId};
+    use funkus_dialogue_core::{
+        AdvanceDialogue, DialoguePlugin, SelectDialogueChoice, StartDialogue, StopDialogue,
+    };
+
+    /// Creates a headless app running the dialogue systems and the dialogue UI.
+    fn ui_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            InputPlugin,
+            DialoguePlugin,
+            DialogueUIPlugin,
+        ))
+        .init_asset::<Image>();
+        app
+    }
+
+    /// Spawns a runner and requests to start a dialogue with the given graph on it.
+    fn spawn_runner(app: &mut App, graph: DialogueGraph, bundle: impl Bundle) -> Entity {
+        let dialogue_handle = app
+            .world_mut()
+            .resource_mut::<Assets<DialogueAsset>>()
+            .add(DialogueAsset::new(graph));
+        let entity = app
+            .world_mut()
+            .spawn((DialogueRunner::default(), bundle))
+            .id();
+        app.world_mut().send_event(StartDialogue {
+            entity,
+            dialogue_handle,
+            entry_point: None,
+        });
+        entity
+    }
+
+    /// Gets the dialogue UI roots linked to a runner.
+    fn linked_uis(app: &mut App, runner: Entity) -> Vec<Entity> {
+        app.world_mut()
+            .query::<(Entity, &DialogueUiLink)>()
+            .iter(app.world())
+            .filter(|(_, link)| link.0 == runner)
+            .map(|(ui, _)| ui)
+            .collect()
+    }
+
+    /// Gets the only dialogue UI root linked to a runner.
+    fn linked_ui(app: &mut App, runner: Entity) -> Entity {
+        let uis = linked_uis(app, runner);
+        assert_eq!(uis.len(), 1, "expected one UI for {:?}", runner);
+        uis[0]
+    }
+
+    /// Finds the child of a dialogue UI root with the given component.
+    fn ui_child<C: Component>(app: &App, ui: Entity) -> Entity {
+        app.world()
+            .get::<Children>(ui)
+            .into_iter()
+            .flatten()
+            .copied()
+            .find(|child| app.world().get::<C>(*child).is_some())
+            .expect("dialogue UI child not found")
+    }
+
+    /// Gets the dialogue text shown by a dialogue UI.
+    fn shown_text(app: &App, ui: Entity) -> String {
+        let text = ui_child::<DialogueText>(app, ui);
+        app.world().get::<Text>(text).unwrap().0.clone()
+    }
+
+    /// Gets the text and color of the choices shown by a dialogue UI.
+    fn shown_choices(app: &App, ui: Entity) -> Vec<(String, Color)> {
+        let container = ui_child::<ChoicesContainer>(app, ui);
+        app.world()
+            .get::<Children>(container)
+            .into_iter()
+            .flatten()
+            .map(|choice| {
+                (
+                    app.world().get::<Text>(*choice).unwrap().0.clone(),
+                    app.world().get::<TextColor>(*choice).unwrap().0,
+                )
+            })
+            .collect()
+    }
+
+    /// Creates a choice node with one choice leading to a text node.
+    fn choice_graph() -> DialogueGraph {
+        let mut graph = DialogueGraph::new(NodeId(1))
+            .with_node(DialogueNode::choice(NodeId(1)))
+            .with_node(DialogueNode::text(NodeId(2), "Onwards"));
+        graph
+            .connect(NodeId(1), NodeId(2), ConnectionData::new(Some("Go".into())))
+            .unwrap();
+        graph
+    }
+
+    #[test]
+    fn test_runner_style_overrides_resource() {
+        let mut app = ui_app();
+        let runner_style = DialogueUiStyle {
+            anchor: DialogueBoxAnchor::Top,
+            height: Val::Px(120.0),
+            choice_color: Color::srgb(1.0, 0.0, 0.0),
+            ..default()
+        };
+        let styled = spawn_runner(&mut app, choice_graph(), runner_style.clone());
+        let plain = spawn_runner(&mut app, choice_graph(), ());
+        app.update();
+        app.update();
+
+        let resource_style = DialogueUiStyle::default();
+        let styled_ui = linked_ui(&mut app, styled);
+        let node = app.world().get::<Node>(styled_ui).unwrap();
+        assert_eq!(node.height, Val::Px(120.0));
+        assert_eq!(node.top, resource_style.margin.top);
+        assert_eq!(node.bottom, Val::Auto);
+        assert_eq!(
+            shown_choices(&app, styled_ui),
+            vec![("1. Go".to_string(), runner_style.choice_color)]
+        );
+
+        let plain_ui = linked_ui(&mut app, plain);
+        let node = app.world().get::<Node>(plain_ui).unwrap();
+        assert_eq!(node.height, resource_style.height);
+        assert_eq!(node.bottom, resource_style.margin.bottom);
+        assert_eq!(
+            shown_choices(&app, plain_ui),
+            vec![("1. Go".to_string(), resource_style.choice_color)]
+        );
+    }
+    #[test]
+    fn test_ui_follows_runner_lifecycle() {
+        let mut graph = DialogueGraph::new(NodeId(1))
+            .with_node(DialogueNode::text(NodeId(1), "Hello"))
+            .with_node(DialogueNode::text(NodeId(2), "Goodbye"));
+        graph
+            .connect(NodeId(1), NodeId(2), ConnectionData::new(None))
+            .unwrap();
+
+        // Both dialogues start in the same frame
+        let mut app = ui_app();
+        let first = spawn_runner(&mut app, graph.clone(), ());
+        let second = spawn_runner(&mut app, graph, ());
+        app.update();
+        app.update();
+        let first_ui = linked_ui(&mut app, first);
+        let second_ui = linked_ui(&mut app, second);
+        assert_ne!(first_ui, second_ui);
+
+        // Each UI shows the line of its own runner
+        app.world_mut()
+            .send_event(AdvanceDialogue { entity: first });
+        app.update();
+        assert_eq!(shown_text(&app, first_ui), "Goodbye");
+        assert_eq!(shown_text(&app, second_ui), "Hello");
+
+        // Finishing a dialogue only despawns its own UI
+        app.world_mut()
+            .send_event(AdvanceDialogue { entity: first });
+        app.update();
+        assert!(linked_uis(&mut app, first).is_empty());
+        assert!(app.world().get_entity(first_ui).is_err());
+        assert_eq!(linked_ui(&mut app, second), second_ui);
+
+        // Stopping a dialogue despawns its UI too
+        app.world_mut().send_event(StopDialogue { entity: second });
+        app.update();
+        assert!(linked_uis(&mut app, second).is_empty());
+    }
+    #[test]
+    fn test_bubbles_stay_on_screen() {
+        let mut app = ui_app();
+        app.add_plugins(WindowPlugin::default())
+            .init_resource::<ManualTextureViews>();
+
+        // Camera at the origin looking at a 1280x720 window
+        app.world_mut().spawn((
+            Camera::default(),
+            OrthographicProjection::default_2d(),
+            GlobalTransform::default(),
+        ));
+        app.world_mut()
+            .run_system_once(camera_system::<OrthographicProjection>)
+            .unwrap();
+
+        let guide_graph = DialogueGraph::new(NodeId(1))
+            .with_node(DialogueNode::text(NodeId(1), "Over here!").with_speaker("guide"));
+        let centered = spawn_runner(&mut app, guide_graph, DialoguePresentation::Bubble);
+        app.world_mut().spawn((
+            DialogueSpeaker::new("guide"),
+            GlobalTransform::from_xyz(100.0, 0.0, 0.0),
+        ));
+        let narrator_graph =
+            DialogueGraph::new(NodeId(1)).with_node(DialogueNode::text(NodeId(1), "Far away..."));
+        let far_right = spawn_runner(
+            &mut app,
+            narrator_graph,
+            (
+                DialoguePresentation::Bubble,
+                GlobalTransform::from_xyz(5000.0, 0.0, 0.0),
+            ),
+        );
+        app.update();
+        app.update();
+
+        // Anchored above the speaker, 60 units up from the window center
+        let style = DialogueUiStyle::default();
+        let ui = linked_ui(&mut app, centered);
+        let node = app.world().get::<Node>(ui).unwrap();
+        assert_eq!(node.left, Val::Px(740.0));
+        assert_eq!(node.top, Val::Px(300.0));
+        assert_eq!(
+            app.world().get::<Visibility>(ui),
+            Some(&Visibility::Inherited)
+        );
+
+        // Lines without a speaker follow the runner, which is off screen
+        let ui = linked_ui(&mut app, far_right);
+        let node = app.world().get::<Node>(ui).unwrap();
+        assert_eq!(node.left, Val::Px(1280.0 - style.bubble.screen_margin));
+        assert_eq!(node.top, Val::Px(300.0));
+
+        // Moving past the top left corner clamps both coordinates
+        app.world_mut()
+            .entity_mut(far_right)
+            .insert(GlobalTransform::from_xyz(-5000.0, 5000.0, 0.0));
+        app.update();
+        let node = app.world().get::<Node>(ui).unwrap();
+        assert_eq!(node.left, Val::Px(style.bubble.screen_margin));
+        assert_eq!(node.top, Val::Px(style.bubble.screen_margin));
+    }
+    /// Sends presses of the given keys.
+    fn press_keys(app: &mut App, keys: impl IntoIterator<Item = Key>) {
+        for logical_key in keys {
+            app.world_mut().send_event(KeyboardInput {
+                key_code: KeyCode::Unidentified(NativeKeyCode::Unidentified),
+                logical_key,
+                state: ButtonState::Pressed,
+                repeat: false,
+                window: Entity::PLACEHOLDER,
+            });
+        }
+    }
+
+    #[test]
+    fn test_input_field_submits_on_enter() {
+        let mut graph = DialogueGraph::new(NodeId(1))
+            .with_node(DialogueNode::input(NodeId(1), "name"))
+            .with_node(DialogueNode::text(NodeId(2), "Nice to meet you."));
+        graph
+            .connect(NodeId(1), NodeId(2), ConnectionData::new(None))
+            .unwrap();
+
+        let mut app = ui_app();
+        let runner = spawn_runner(&mut app, graph, ());
+        app.update();
+        app.update();
+        let ui = linked_ui(&mut app, runner);
+        let field = ui_child::<DialogueInputField>(&app, ui);
+
+        press_keys(
+            &mut app,
+            [
+                Key::Character("A".into()),
+                Key::Character("dz".into()),
+                Key::Backspace,
+                Key::Character("a".into()),
+            ],
+        );
+        app.update();
+        assert_eq!(app.world().get::<Text>(field).unwrap().0, "> Ada_");
+
+        // Enter submits the typed text and empties the field
+        press_keys(&mut app, [Key::Enter]);
+        app.update();
+        let submitted: Vec<_> = app
+            .world()
+            .resource::<Events<SubmitDialogueInput>>()
+            .iter_current_update_events()
+            .map(|ev| (ev.entity, ev.value.clone()))
+            .collect();
+        assert_eq!(submitted, vec![(runner, "Ada".to_string())]);
+        assert!(app
+            .world()
+            .get::<DialogueInputField>(field)
+            .unwrap()
+            .value
+            .is_empty());
+
+        // The dialogue moves on and the field is cleared
+        app.update();
+        let runner = app.world().get::<DialogueRunner>(runner).unwrap();
+        assert_eq!(
+            runner.variables.get("name").map(String::as_str),
+            Some("Ada")
+        );
+        assert_eq!(runner.state, DialogueState::ShowingText);
+        assert_eq!(app.world().get::<Text>(field).unwrap().0, "");
+    }
+    #[test]
+    fn test_hub_choices_rebuilt_on_return() {
+        let mut graph = DialogueGraph::new(NodeId(1))
+            .with_node(DialogueNode::choice(NodeId(1)).with_hub(true).unwrap())
+            .with_node(DialogueNode::return_to_hub(NodeId(2)))
+            .with_node(DialogueNode::text(NodeId(3), "Farewell."));
+        let question = ConnectionData::new(Some("Ask".into())).with_once(true);
+        graph.connect(NodeId(1), NodeId(2), question).unwrap();
+        graph
+            .connect(
+                NodeId(1),
+                NodeId(3),
+                ConnectionData::new(Some("Bye".into())),
+            )
+            .unwrap();
+
+        let mut app = ui_app();
+        let runner = spawn_runner(&mut app, graph, ());
+        app.update();
+        app.update();
+        let ui = linked_ui(&mut app, runner);
+        let style = DialogueUiStyle::default();
+        assert_eq!(
+            shown_choices(&app, ui),
+            vec![
+                ("1. Ask".to_string(), style.choice_color),
+                ("2. Bye".to_string(), style.choice_color),
+            ]
+        );
+
+        // Taking the once-only choice returns straight to the hub without it
+        app.world_mut().send_event(SelectDialogueChoice {
+            entity: runner,
+            choice_index: 0,
+        });
+        app.update();
+        app.world_mut()
+            .send_event(AdvanceDialogue { entity: runner });
+        app.update();
+        assert_eq!(
+            app.world().get::<DialogueRunner>(runner).unwrap().state,
+            DialogueState::WaitingForChoice
+        );
+        assert_eq!(
+            shown_choices(&app, ui),
+            vec![("1. Bye".to_string(), style.choice_color)]
+        );
+    }
+
+    #[test]
+    fn test_looping_text_node_redrawn() {
+        let node = DialogueNode::text(NodeId(1), "Nice weather.")
+            .with_variants(TextVariantMode::Cycle, ["Still nice."])
+            .unwrap();
+        let mut graph = DialogueGraph::new(NodeId(1)).with_node(node);
+        graph
+            .connect(NodeId(1), NodeId(1), ConnectionData::new(None))
+            .unwrap();
+
+        let mut app = ui_app();
+        let runner = spawn_runner(&mut app, graph, ());
+        app.update();
+        app.update();
+        let ui = linked_ui(&mut app, runner);
+        assert_eq!(shown_text(&app, ui), "Nice weather.");
+
+        app.world_mut()
+            .send_event(AdvanceDialogue { entity: runner });
+        app.update();
+        assert_eq!(shown_text(&app, ui), "Still nice.");
     }
 }
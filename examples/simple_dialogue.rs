@@ -26,18 +26,37 @@ fn main() {
     #[cfg(feature = "debug_ui")]
     app.add_plugins(DialogueDebugBundle);
 
+    // Conditionally add the editor if the feature is enabled
+    #[cfg(feature = "editor")]
+    app.add_plugins(DialogueEditorPlugin);
+
     app.run();
 }
 /// Sets up the example scene.
-fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut start_events: EventWriter<StartDialogue>,
+) {
     // Create a camera
     commands.spawn(Camera2d);
 
     // Create an entity to run the dialogue
-    commands.spawn((Name::new("Guide Conversation"), DialogueRunner::default()));
-
-    // Load a dialogue asset
-    let dialogue_handle = asset_server.load("dialogues/example.dialogue.json");
+    let entity = commands
+        .spawn((Name::new("Guide Conversation"), DialogueRunner::default()))
+        .id();
+
+    // Load a dialogue asset and start it, the dialogue begins once the asset is loaded
+    start_events.send(StartDialogue {
+        entity,
+        dialogue_handle: asset_server.load("dialogues/example.dialogue.json"),
+        entry_point: None,
+    });
+
+    // Load the characters referenced by the dialogue's speakers
+    commands.insert_resource(DialogueCharacters(
+        asset_server.load("dialogues/example.characters.json"),
+    ));
 
     // Print a message about controls
     info!("Press SPACE to advance dialogue or confirm choices, 1-9 to select choices, ESC to exit");
@@ -90,51 +109,23 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
         },
         LoadingText,
     ));
-
-    // Spawn the dialogue UI
-    spawn_dialogue_ui(&mut commands);
-
-    // Schedule the dialogue to start once loaded
-    commands.insert_resource(DialogueToStart(dialogue_handle));
 }
 
-// Resource to track the dialogue to start
-#[derive(Resource)]
-struct DialogueToStart(Handle<DialogueAsset>);
-
 /// System to handle keyboard input.
 fn keyboard_input(
     mut commands: Commands,
-    dialogue_assets: Res<Assets<DialogueAsset>>,
     keyboard_input: Res<ButtonInput<KeyCode>>,
-    dialogue_to_start: Option<Res<DialogueToStart>>,
     mut dialogue_query: Query<(Entity, &mut DialogueRunner)>,
+    mut started_events: EventReader<DialogueStarted>,
     mut advance_events: EventWriter<AdvanceDialogue>,
     mut select_events: EventWriter<SelectDialogueChoice>,
-    mut start_events: EventWriter<StartDialogue>,
     mut stop_events: EventWriter<StopDialogue>,
     text_query: Query<Entity, With<LoadingText>>,
 ) {
-    // Check if we need to start the dialogue
-    if let Some(dialogue_to_start) = dialogue_to_start.as_ref() {
-        if dialogue_assets.contains(&dialogue_to_start.0) {
-            // Get the dialogue entity
-            if let Some((entity, _)) = dialogue_query.iter().next() {
-                // Start the dialogue
-                start_events.send(StartDialogue {
-                    entity,
-                    dialogue_handle: dialogue_to_start.0.clone(),
-                });
-
-                // Remove the resource
-                commands.remove_resource::<DialogueToStart>();
-
-                // Update the loading text
-                for loading_entity in text_query.iter() {
-                    // Remove the loading text
-                    commands.entity(loading_entity).despawn();
-                }
-            }
+    // Remove the loading text once the dialogue has started
+    if started_events.read().next().is_some() {
+        for loading_entity in text_query.iter() {
+            commands.entity(loading_entity).despawn();
         }
     }
 